@@ -1,8 +1,9 @@
 use crate::commands::{
-    AddArgs, ApplyArgs, BranchArgs, CheckoutArgs, CiArgs, CleanArgs, CloneArgs, CommitArgs,
-    CreateArgs, FetchArgs, HookArgs, InitArgs, InviteArgs, MakeArgs, MergeArgs, PullArgs, PushArgs,
-    RemoveArgs, RenameArgs, SetArgs, ShowArgs, StatusArgs, TemplateArgs, TopicArgs, TransferArgs,
-    WorkflowArgs,
+    AddArgs, ApplyArgs, ApplyConfigArgs, BranchArgs, CheckoutArgs, CiArgs, CleanArgs, CloneArgs,
+    CommitArgs, CreateArgs, FetchArgs, GrepArgs, HookArgs, InitArgs, InviteArgs, IssueArgs,
+    LabelArgs, LogArgs, MakeArgs, MergeArgs, MilestoneArgs, PlanArgs, PrArgs, PullArgs, PushArgs,
+    ReleaseArgs, RemoveArgs, RenameArgs, ReplaceArgs, SetArgs, ShowArgs, SnapshotArgs, StashArgs,
+    StatusArgs, SyncArgs, TagArgs, TeamArgs, TemplateArgs, TopicArgs, TransferArgs, WorkflowArgs,
 };
 use structopt::StructOpt;
 
@@ -19,6 +20,8 @@ pub enum Commands {
     Add(AddArgs),
     #[structopt(name = "apply", aliases = &["ap"])]
     Apply(ApplyArgs),
+    #[structopt(name = "apply-config")]
+    ApplyConfig(ApplyConfigArgs),
     #[structopt(name = "branch", aliases = &["br"])]
     Branch(BranchArgs),
     #[structopt(name = "checkout", aliases = &["co"])]
@@ -35,30 +38,58 @@ pub enum Commands {
     Create(CreateArgs),
     #[structopt(name = "fetch")]
     Fetch(FetchArgs),
+    #[structopt(name = "grep")]
+    Grep(GrepArgs),
     #[structopt(name = "hook")]
     Hook(HookArgs),
     #[structopt(name = "init")]
     Init(InitArgs),
     #[structopt(name = "invite")]
     Invite(InviteArgs),
+    #[structopt(name = "issue")]
+    Issue(IssueArgs),
+    #[structopt(name = "label")]
+    Label(LabelArgs),
+    #[structopt(name = "log")]
+    Log(LogArgs),
     #[structopt(name = "make")]
     Make(MakeArgs),
     #[structopt(name = "merge")]
     Merge(MergeArgs),
+    #[structopt(name = "milestone")]
+    Milestone(MilestoneArgs),
+    #[structopt(name = "plan")]
+    Plan(PlanArgs),
+    #[structopt(name = "pr")]
+    Pr(PrArgs),
     #[structopt(name = "pull")]
     Pull(PullArgs),
     #[structopt(name = "push")]
     Push(PushArgs),
+    #[structopt(name = "release")]
+    Release(ReleaseArgs),
     #[structopt(name = "remove")]
     Remove(RemoveArgs),
     #[structopt(name = "rename")]
     Rename(RenameArgs),
+    #[structopt(name = "replace")]
+    Replace(ReplaceArgs),
     #[structopt(name = "set")]
     Set(SetArgs),
     #[structopt(name = "show")]
     Show(ShowArgs),
+    #[structopt(name = "snapshot")]
+    Snapshot(SnapshotArgs),
+    #[structopt(name = "stash")]
+    Stash(StashArgs),
     #[structopt(name = "status")]
     Status(StatusArgs),
+    #[structopt(name = "sync")]
+    Sync(SyncArgs),
+    #[structopt(name = "tag")]
+    Tag(TagArgs),
+    #[structopt(name = "team")]
+    Team(TeamArgs),
     #[structopt(name = "template")]
     Template(TemplateArgs),
     #[structopt(name = "topic")]
@@ -36,6 +36,7 @@ impl AddRepoArgs {
     pub fn run(&self) -> Result<()> {
         let user = common::user()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user.token)?;
 
         let filtered_repos =
             common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user.token)?;
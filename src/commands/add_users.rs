@@ -41,6 +41,7 @@ impl AddUsersArgs {
     fn add_users_to_org(&self) -> Result<()> {
         let user_token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
 
         let users: Vec<String> = self.users.iter().map(|s| s.to_string()).collect();
 
@@ -54,6 +55,7 @@ impl AddUsersArgs {
     fn add_users_to_team(&self, team_name: &str) -> Result<()> {
         let user_token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
 
         let users: Vec<String> = self.users.iter().map(|s| s.to_string()).collect();
 
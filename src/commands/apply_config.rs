@@ -0,0 +1,279 @@
+use super::common;
+use super::models::OrgConfig;
+use super::plan::{plan, print_plan, Change, OrgState};
+use super::team_helper::{MemberChange, TeamMembership};
+use crate::github;
+use crate::github::RemoteRepo;
+use anyhow::{anyhow, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Make an organisation match an organisation file
+///
+/// The changes are the same as the ones shown by plan. They are shown first and only applied
+/// after a confirmation. Changes are applied one by one, repositories first, then teams,
+/// members and permissions, and branch protection last.
+pub struct ApplyConfigArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short, default_value = "org.toml")]
+    /// Path to the organisation file
+    pub file: PathBuf,
+}
+
+impl ApplyConfigArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+        let config = OrgConfig::get(&self.file)?;
+
+        let state = OrgState::fetch(&organisation, &config, &user_token)?;
+        let changes = plan(&config, &state);
+
+        print_plan(&organisation, &config, &state, &changes);
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        if !confirm(changes.len(), &organisation)? {
+            println!("Command is aborted. Nothing is changed!");
+            return Ok(());
+        }
+
+        let mut applier = Applier::new(&organisation, &state, &user_token);
+        let statuses: Vec<_> = changes
+            .iter()
+            .map(|c| Status {
+                change: c.to_string(),
+                result: applier.apply(c),
+            })
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+/// Applies changes in order, keeping track of the repositories and teams it creates
+struct Applier<'a> {
+    org: &'a str,
+    token: &'a str,
+    repos: BTreeMap<String, RemoteRepo>,
+    /// Team name of the organisation file to team slug
+    teams: BTreeMap<String, String>,
+}
+
+impl<'a> Applier<'a> {
+    fn new(org: &'a str, state: &OrgState, token: &'a str) -> Applier<'a> {
+        Applier {
+            org,
+            token,
+            repos: state
+                .repos
+                .iter()
+                .map(|(name, s)| (name.clone(), s.remote.clone()))
+                .collect(),
+            teams: state
+                .teams
+                .iter()
+                .map(|(name, s)| (name.clone(), s.slug.clone()))
+                .collect(),
+        }
+    }
+
+    fn repo(&self, name: &str) -> Result<&RemoteRepo> {
+        self.repos
+            .get(name)
+            .ok_or_else(|| anyhow!("Repo {} doesn't exist", name))
+    }
+
+    fn team(&self, name: &str) -> Result<&str> {
+        self.teams
+            .get(name)
+            .map(|s| s.as_str())
+            .ok_or_else(|| anyhow!("Team {} doesn't exist", name))
+    }
+
+    /// GitHub makes the user creating a team one of its maintainers, give them the role they
+    /// have in the organisation file, or remove them if they aren't in it
+    fn fix_creator(&self, slug: &str, maintainers: &[String], members: &[String]) -> Result<()> {
+        let contains = |list: &[String], user: &str| {
+            list.iter().any(|u| u.to_lowercase() == user.to_lowercase())
+        };
+
+        let current = TeamMembership::fetch(self.org, slug, self.token)?;
+        for user in &current.maintainers {
+            if contains(maintainers, user) {
+                continue;
+            }
+            if contains(members, user) {
+                github::add_user_to_team(self.org, slug, "member", user, self.token)?;
+            } else {
+                github::remove_user_from_team(self.org, slug, user, self.token)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply(&mut self, change: &Change) -> Result<()> {
+        log::info!("Applying {}", change);
+
+        match change {
+            Change::CreateRepo { repo, public } => {
+                let response = github::create_org_repo(self.org, repo, *public, self.token)?;
+                let remote = RemoteRepo {
+                    name: repo.clone(),
+                    owner: self.org.to_string(),
+                    ssh_url: response.ssh_url,
+                    https_url: response.clone_url,
+                    default_branch: None,
+                };
+                self.repos.insert(repo.clone(), remote);
+            }
+            Change::SetVisibility { repo, public } => {
+                github::set_repo_visibility(self.repo(repo)?, !public, self.token)?
+            }
+            Change::SetDescription { repo, description } => {
+                github::set_repo_metadata(self.repo(repo)?, Some(description), None, self.token)?
+            }
+            Change::SetTopics { repo, topics } => {
+                github::set_topics(self.repo(repo)?, topics, self.token)?;
+            }
+            Change::SetDefaultBranch { repo, branch } => {
+                github::set_default_branch(self.repo(repo)?, branch, self.token)?
+            }
+            Change::CreateTeam {
+                team,
+                description,
+                secret,
+                maintainers,
+                members,
+            } => {
+                let response = github::create_team(
+                    self.org,
+                    team,
+                    description,
+                    maintainers.clone(),
+                    *secret,
+                    None,
+                    self.token,
+                )?;
+                self.teams.insert(team.clone(), response.slug.clone());
+                self.fix_creator(&response.slug, maintainers, members)?;
+            }
+            Change::Member { team, change } => {
+                let team = self.team(team)?;
+                match change {
+                    MemberChange::Add { user, role } | MemberChange::SetRole { user, role } => {
+                        github::add_user_to_team(self.org, team, role, user, self.token)?
+                    }
+                    MemberChange::Remove(user) => {
+                        github::remove_user_from_team(self.org, team, user, self.token)?
+                    }
+                }
+            }
+            Change::SetPermission {
+                team,
+                repo,
+                permission,
+            } => github::set_team_permission(
+                self.org,
+                self.team(team)?,
+                self.org,
+                repo,
+                permission,
+                self.token,
+            )?,
+            Change::ProtectBranch { repo, branch } => {
+                github::set_protected_branch(self.repo(repo)?, branch, self.token)?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn confirm(changes: usize, org: &str) -> Result<bool> {
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to make {} change(s) in organisation {}?\nEnter {} to continue",
+            changes, org, key
+        ),
+        key,
+    )
+}
+
+struct Status {
+    change: String,
+    result: Result<(), Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(l -> &self.change), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(_) => cell!(Fgl -> "Done"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.change), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn summarize(statuses: &[Status]) {
+    let rows: Vec<_> = statuses.iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Change", "Result"]);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+
+    if errors.is_empty() {
+        let msg = format!("\nApplied {} changes successfully!", statuses.len());
+        println!("{}", msg.green());
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Change", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
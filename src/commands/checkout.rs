@@ -1,12 +1,15 @@
 use super::common;
+use super::models::{RepoSnapshot, Snapshot};
+use crate::date;
 use crate::git;
+use crate::path;
 use crate::user::User;
 
 use crate::git::GitCredential;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 
 use crate::filter::Filter;
-use git2::BranchType;
+use git2::{BranchType, Repository};
 use structopt::StructOpt;
 
 use crate::commands::topic_helper;
@@ -17,9 +20,16 @@ use crate::github::RemoteRepo;
 /// Checkout a branch all repositories that their name matches a pattern or
 /// a topic
 ///
-/// This command is able to checkout a local branch as well as a remote branch
+/// This command is able to checkout a local branch as well as a remote branch. Local branches
+/// created from a remote branch will track that remote branch.
+///
+/// This command refuses to checkout a repository that has uncommitted changes to tracked files
+/// unless --stash is used. Untracked files are left in place
 ///
 /// This command is able to clone a repository if it is not on the root directory
+///
+/// Use --at to checkout all repositories as they were at a date or a tag, and --restore to go
+/// back to where they were before
 pub struct CheckoutArgs {
     #[structopt(long, short)]
     /// Target organisation name
@@ -29,18 +39,38 @@ pub struct CheckoutArgs {
     #[structopt(long, short)]
     /// Optional regex to filter repositories
     pub regex: Option<Filter>,
-    #[structopt(long, required_unless("regex"))]
+    #[structopt(long, required_unless_one(&["regex", "restore"]))]
     /// topic to filter
     pub topic: Option<String>,
-    #[structopt(long, short)]
+    #[structopt(long, short, required_unless("restore"))]
     /// branch name to checkout
-    pub branch: String,
+    pub branch: Option<String>,
     #[structopt(long)]
     /// Use this option to checkout a remote banch
     ///
     /// If this option is not provided, the command will report that the target branch is remote
     /// only
     pub remote: bool,
+    #[structopt(long, short, conflicts_with = "at")]
+    /// Option to create the branch when it exists neither locally nor remotely
+    pub create: bool,
+    #[structopt(long, requires = "create")]
+    /// Base revision of the created branch, default is the current HEAD
+    pub base: Option<String>,
+    #[structopt(long, short, conflicts_with = "at")]
+    /// Option to stash uncommitted changes before checkout and bring them back after
+    pub stash: bool,
+    #[structopt(long, conflicts_with = "restore")]
+    /// Checkout the last commit of the branch before a date or a tag
+    ///
+    /// Date is in UTC and in the format YYYY-MM-DD, YYYY-MM-DDTHH:MM or YYYY-MM-DDTHH:MM:SS. The
+    /// time of a tag is taken from the first matched repository that has the tag.
+    ///
+    /// HEAD will be detached at the found commits.
+    pub at: Option<String>,
+    #[structopt(long)]
+    /// Go back to the branches or commits that repositories were on before `checkout --at`
+    pub restore: bool,
     #[structopt(long, short)]
     /// Option to use https instead of ssh when clone repositories
     pub use_https: bool,
@@ -51,6 +81,15 @@ impl CheckoutArgs {
         let user = common::user()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
 
+        if self.restore {
+            return restore(&organisation);
+        }
+
+        let branch = self
+            .branch
+            .as_deref()
+            .ok_or_else(|| anyhow!("Branch name is required"))?;
+
         let all_repos = topic_helper::query_repositories_with_topics(&organisation, &user.token)?;
 
         let filtered_repos: Vec<_> =
@@ -67,49 +106,328 @@ impl CheckoutArgs {
             return Ok(());
         }
 
+        if let Some(at) = &self.at {
+            return self.checkout_at(at, branch, &filtered_repos, &organisation, &user);
+        }
+
         for repo in filtered_repos {
-            match checkout_branch(
-                &repo,
-                &self.branch,
-                &user,
-                &"origin",
-                self.remote,
-                self.use_https,
-            ) {
-                Ok(_) => println!(
-                    "Checkout branch {} of repo {:?} successfully",
-                    &self.branch, repo.name
+            match self.checkout_branch(&repo, branch, &user, &"origin") {
+                Ok(checkout) => println!(
+                    "Checkout branch {} of repo {:?} successfully{}",
+                    branch,
+                    repo.name,
+                    checkout.describe()
                 ),
                 Err(e) => println!(
                     "Failed to checkout branch {} of repo {:?} because {:?}",
-                    &self.branch, repo.name, e
+                    branch, repo.name, e
                 ),
             }
         }
 
         Ok(())
     }
+
+    fn checkout_branch(
+        &self,
+        repo: &RemoteRepo,
+        branch: &str,
+        user: &User,
+        remote_name: &str,
+    ) -> Result<Checkout> {
+        let git_repo = try_from_one(repo.clone(), user, self.use_https)?;
+        let mut git_repo = git_repo.open()?;
+
+        // Untracked files are carried over to the new branch, like git does
+        let status = git::status(&git_repo, false)?;
+        let stashed = if !status.has_tracked_changes() {
+            false
+        } else if !status.conflicted.is_empty() {
+            return Err(anyhow!(
+                "Repository has conflicts. Please resolve them first."
+            ));
+        } else if self.stash {
+            git::stash(&mut git_repo, Some("gut checkout"))?;
+            true
+        } else {
+            return Err(anyhow!(
+                "Repository has uncommitted changes. Use `--stash` to stash them before checkout."
+            ));
+        };
+
+        let mut checkout = self.switch_branch(&git_repo, branch, user, remote_name);
+
+        if stashed {
+            // changes go back to the new branch, or to the old one if checkout failed
+            if let Err(e) = git::stash_pop(&mut git_repo, 0) {
+                return Err(anyhow!(
+                    "Stashed changes cannot be applied because {:?}. They are kept in the stash.",
+                    e
+                ));
+            }
+            if let Ok(c) = &mut checkout {
+                c.stashed = true;
+            }
+        }
+
+        checkout
+    }
+
+    fn switch_branch(
+        &self,
+        git_repo: &Repository,
+        branch: &str,
+        user: &User,
+        remote_name: &str,
+    ) -> Result<Checkout> {
+        if git_repo.find_branch(branch, BranchType::Local).is_ok() {
+            git::checkout_local_branch(git_repo, branch)?;
+            let tracked = git::track_remote_branch(git_repo, branch, remote_name)?;
+            return Ok(Checkout::new(if tracked {
+                Source::Tracked
+            } else {
+                Source::Local
+            }));
+        }
+
+        if !self.remote && !self.create {
+            return Err(anyhow!("There is no local branch with name: {}.\n You can use `--remote` option to checkout a remote branch or `--create` to create it.", branch));
+        }
+
+        let cred = GitCredential::from(user);
+        git::fetch_all(git_repo, remote_name, Some(cred.clone()))?;
+
+        let remote_branch = format!("{}/{}", remote_name, branch);
+        if git_repo
+            .find_branch(&remote_branch, BranchType::Remote)
+            .is_ok()
+        {
+            git::checkout_remote_branch(git_repo, branch, remote_name, Some(cred))?;
+            return Ok(Checkout::new(Source::Remote));
+        }
+
+        if !self.create {
+            return Err(anyhow!("There is no remote branch named: {}", branch));
+        }
+
+        let base = self.base.as_deref().unwrap_or("HEAD");
+        let commit = git_repo
+            .revparse_single(base)
+            .and_then(|obj| obj.peel_to_commit())
+            .with_context(|| format!("Cannot find base {}", base))?;
+        git_repo.branch(branch, &commit, false)?;
+        git::checkout_local_branch(git_repo, branch)?;
+        Ok(Checkout::new(Source::Created))
+    }
+
+    fn checkout_at(
+        &self,
+        at: &str,
+        branch: &str,
+        repos: &[RemoteRepo],
+        organisation: &str,
+        user: &User,
+    ) -> Result<()> {
+        let git_repos: Vec<_> = repos
+            .iter()
+            .map(|r| {
+                let git_repo = try_from_one(r.clone(), user, self.use_https)
+                    .and_then(|g| g.open().map_err(|e| e.into()));
+                (r, git_repo)
+            })
+            .collect();
+
+        let time = match date::parse_timestamp(at) {
+            Some(time) => time,
+            None => find_tag_time(&git_repos, at)?,
+        };
+
+        let restore_path = path::checkout_restore_path(organisation)
+            .ok_or_else(|| anyhow!("Cannot find the config directory"))?;
+        let mut snapshot = if restore_path.exists() {
+            Snapshot::get(&restore_path)?
+        } else {
+            Snapshot {
+                organisation: organisation.to_string(),
+                repos: vec![],
+            }
+        };
+
+        for (repo, git_repo) in git_repos {
+            let git_repo = match git_repo {
+                Ok(git_repo) => git_repo,
+                Err(e) => {
+                    println!("Failed to open repo {:?} because {:?}", repo.name, e);
+                    continue;
+                }
+            };
+
+            match checkout_at(&git_repo, &repo.name, branch, time, &mut snapshot) {
+                Ok(sha) => println!(
+                    "Checkout repo {:?} at commit {} of branch {} successfully",
+                    repo.name,
+                    sha.get(..8).unwrap_or(&sha),
+                    branch
+                ),
+                Err(e) => println!(
+                    "Failed to checkout repo {:?} at {} because {:?}",
+                    repo.name, at, e
+                ),
+            }
+        }
+
+        snapshot.save(&restore_path)?;
+        println!("\nUse `gut checkout --restore` to go back");
+
+        Ok(())
+    }
+}
+
+struct Checkout {
+    source: Source,
+    stashed: bool,
+}
+
+enum Source {
+    Local,
+    Tracked,
+    Remote,
+    Created,
+}
+
+impl Checkout {
+    fn new(source: Source) -> Checkout {
+        Checkout {
+            source,
+            stashed: false,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let source = match self.source {
+            Source::Local => "",
+            Source::Tracked => ", upstream is set",
+            Source::Remote => ", created from remote branch",
+            Source::Created => ", created as a new branch",
+        };
+        let stashed = if self.stashed {
+            ", uncommitted changes are brought back"
+        } else {
+            ""
+        };
+        format!("{}{}", source, stashed)
+    }
 }
 
-fn checkout_branch(
-    repo: &RemoteRepo,
+fn find_tag_time(git_repos: &[(&RemoteRepo, Result<Repository>)], tag: &str) -> Result<i64> {
+    for (repo, git_repo) in git_repos {
+        if let Ok(git_repo) = git_repo {
+            if let Some(time) = git::tag_time(git_repo, tag)? {
+                println!("Use time of tag {} in repo {:?}", tag, repo.name);
+                return Ok(time);
+            }
+        }
+    }
+
+    Err(anyhow!(
+        "{} is neither a valid date nor a tag of any matched repository",
+        tag
+    ))
+}
+
+/// Detach HEAD at the last commit of a branch before `time`
+///
+/// The current position will be added to the snapshot if it is not there yet
+fn checkout_at(
+    git_repo: &Repository,
+    name: &str,
     branch: &str,
-    user: &User,
-    remote_name: &str,
-    remote: bool,
-    use_https: bool,
-) -> Result<()> {
-    let git_repo = try_from_one(repo.clone(), user, use_https)?;
-    let git_repo = git_repo.open()?;
-
-    if git_repo.find_branch(branch, BranchType::Local).is_ok() {
-        git::checkout_local_branch(&git_repo, branch)?;
-    } else if remote {
-        let cred = GitCredential::from(user);
-        git::checkout_remote_branch(&git_repo, branch, remote_name, Some(cred))?;
+    time: i64,
+    snapshot: &mut Snapshot,
+) -> Result<String> {
+    if git::status(git_repo, false)?.has_tracked_changes() {
+        return Err(anyhow!(
+            "Repository has uncommitted changes. Please commit or stash them first."
+        ));
+    }
+
+    let tip = git_repo
+        .find_branch(branch, BranchType::Local)
+        .or_else(|_| git_repo.find_branch(&format!("origin/{}", branch), BranchType::Remote))
+        .map_err(|_| anyhow!("There is no branch with name: {}", branch))?
+        .get()
+        .target()
+        .ok_or_else(|| anyhow!("Branch {} is not a direct reference", branch))?;
+
+    let oid = git::last_commit_before(git_repo, tip, time)?
+        .ok_or_else(|| anyhow!("There is no commit on branch {} before that time", branch))?;
+
+    if !snapshot.repos.iter().any(|r| r.name == name) {
+        snapshot
+            .repos
+            .push(RepoSnapshot::from_repo(name, git_repo)?);
+    }
+
+    let sha = oid.to_string();
+    git::checkout_detached(git_repo, &sha)?;
+    Ok(sha)
+}
+
+/// Checkout the positions saved by `checkout --at`
+fn restore(organisation: &str) -> Result<()> {
+    let root = common::root()?;
+    let restore_path = path::checkout_restore_path(organisation)
+        .ok_or_else(|| anyhow!("Cannot find the config directory"))?;
+
+    if !restore_path.exists() {
+        println!(
+            "There is nothing to restore for organisation {}",
+            organisation
+        );
+        return Ok(());
+    }
+
+    let snapshot = Snapshot::get(&restore_path)?;
+    let mut remaining = vec![];
+
+    for repo in snapshot.repos {
+        let local_path = path::local_path_repo(organisation, &repo.name, &root);
+        match restore_one(&local_path, &repo) {
+            Ok(_) => println!("Restore repo {:?} successfully", repo.name),
+            Err(e) => {
+                println!("Failed to restore repo {:?} because {:?}", repo.name, e);
+                remaining.push(repo);
+            }
+        }
+    }
+
+    if remaining.is_empty() {
+        std::fs::remove_file(&restore_path)?;
     } else {
-        return Err(anyhow!("There is no local branch with name: {}.\n You can use `--remote` option to checkout a remote branch.", branch));
-    };
+        let snapshot = Snapshot {
+            organisation: snapshot.organisation,
+            repos: remaining,
+        };
+        snapshot.save(&restore_path)?;
+    }
 
     Ok(())
 }
+
+fn restore_one(local_path: &std::path::PathBuf, repo: &RepoSnapshot) -> Result<()> {
+    let git_repo = git::open(local_path)
+        .with_context(|| format!("{:?} is not a git directory.", local_path))?;
+
+    if git::status(&git_repo, false)?.has_tracked_changes() {
+        return Err(anyhow!(
+            "Repository has uncommitted changes. Please commit or stash them first."
+        ));
+    }
+
+    match &repo.branch {
+        Some(branch) if git_repo.find_branch(branch, BranchType::Local).is_ok() => {
+            git::checkout_local_branch(&git_repo, branch)
+        }
+        _ => git::checkout_detached(&git_repo, &repo.sha),
+    }
+}
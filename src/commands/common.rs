@@ -6,7 +6,7 @@ use std::path::PathBuf;
 use std::process::{Command, Output};
 
 use crate::github;
-use crate::github::{NoReposFound, RemoteRepo, Unauthorized};
+use crate::github::{NoReposFound, NotAnOrganisation, OwnerType, RemoteRepo, Unauthorized};
 
 use crate::filter::{Filter, Filterable};
 use crate::user::User;
@@ -50,8 +50,30 @@ pub fn organisation(opt: Option<&str>) -> Result<String> {
     }
 }
 
+/// Make sure that the owner is an organisation
+///
+/// Use this for commands that only make sense for organisations (teams, members, invitations...)
+pub fn ensure_organisation(owner: &str, token: &str) -> Result<()> {
+    match owner_type(owner, token)? {
+        OwnerType::Organisation => Ok(()),
+        OwnerType::User => Err(NotAnOrganisation(owner.to_string()).into()),
+    }
+}
+
+pub fn owner_type(owner: &str, token: &str) -> Result<OwnerType> {
+    match github::get_owner_type(owner, token) {
+        Ok(t) => Ok(t),
+        Err(e) => {
+            if e.downcast_ref::<Unauthorized>().is_some() {
+                anyhow::bail!("User token invalid. Run `gut init` with a valid token");
+            }
+            Err(e).with_context(|| format!("Cannot find user or organisation {}", owner))
+        }
+    }
+}
+
 fn remote_repos(token: &str, org: &str) -> Result<Vec<RemoteRepo>> {
-    match github::list_org_repos(token, org).context("When fetching repositories") {
+    match github::list_owner_repos(token, org).context("When fetching repositories") {
         Ok(repos) => Ok(repos),
         Err(e) => {
             if e.downcast_ref::<NoReposFound>().is_some() {
@@ -32,6 +32,7 @@ impl CreateDiscussionArgs {
     pub fn create_discusstion(&self) -> Result<()> {
         let token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &token)?;
 
         match github::create_discusstion(
             &organisation,
@@ -1,5 +1,5 @@
 use super::common;
-use crate::github::create_org_repo;
+use crate::github::{create_org_repo, create_user_repo, CreateRepoResponse, OwnerType};
 use crate::user::User;
 use std::path::PathBuf;
 
@@ -13,6 +13,8 @@ use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 /// Create new repositories in an organisation and push for existing git repositories
+///
+/// The owner can also be your own user account
 pub struct CreateRepoArgs {
     #[structopt(long, short)]
     /// Target organisation name
@@ -188,7 +190,7 @@ fn create_repo(
         .to_str()
         .ok_or_else(|| anyhow!("{:?} doesn not have a valid name", dir))?;
 
-    let created_repo = create_org_repo(org, repo_name, public, &user.token)?;
+    let created_repo = create_owner_repo(org, repo_name, public, user)?;
     log::debug!("new created repo: {:?}", created_repo.html_url);
 
     let remote_url = if use_https {
@@ -214,6 +216,29 @@ fn create_repo(
     Ok(create_repo)
 }
 
+/// Create a repository under an organisation, or under the authenticated user's account
+/// when the owner is a user account
+fn create_owner_repo(
+    owner: &str,
+    name: &str,
+    public: bool,
+    user: &User,
+) -> Result<CreateRepoResponse> {
+    match common::owner_type(owner, &user.token)? {
+        OwnerType::Organisation => create_org_repo(owner, name, public, &user.token),
+        OwnerType::User => {
+            if !owner.eq_ignore_ascii_case(&user.username) {
+                return Err(anyhow!(
+                    "{} is a user account. You can only create repositories for your own account ({})",
+                    owner,
+                    user.username
+                ));
+            }
+            create_user_repo(name, public, &user.token)
+        }
+    }
+}
+
 #[derive(Debug)]
 struct CreateRepo {
     name: String,
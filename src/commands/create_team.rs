@@ -26,6 +26,9 @@ pub struct CreateTeamArgs {
     #[structopt(long, short)]
     /// List of usernames to invite to the new created team
     pub members: Vec<String>,
+    #[structopt(long)]
+    /// Slug or name of the parent team, to create a nested team
+    pub parent: Option<String>,
 }
 
 impl CreateTeamArgs {
@@ -54,6 +57,12 @@ fn create_team(args: &CreateTeamArgs, token: &str) -> Result<CreateTeamResponse>
     let des: &str = args.description.as_ref().unwrap_or(empty);
     let members: Vec<String> = args.members.iter().map(|s| s.to_string()).collect();
     let organisation = common::organisation(args.organisation.as_deref())?;
+    common::ensure_organisation(&organisation, token)?;
+
+    let parent = match &args.parent {
+        Some(parent) => Some(parent_id(&organisation, parent, token)?),
+        None => None,
+    };
 
     match github::create_team(
         &organisation,
@@ -61,6 +70,7 @@ fn create_team(args: &CreateTeamArgs, token: &str) -> Result<CreateTeamResponse>
         des,
         members,
         args.secret,
+        parent,
         token,
     ) {
         Ok(response) => Ok(response),
@@ -72,3 +82,11 @@ fn create_team(args: &CreateTeamArgs, token: &str) -> Result<CreateTeamResponse>
         }
     }
 }
+
+fn parent_id(org: &str, parent: &str, token: &str) -> Result<i32> {
+    github::get_teams(org, token)?
+        .iter()
+        .find(|t| t.slug == parent || t.name == parent)
+        .map(|t| t.id)
+        .ok_or_else(|| anyhow::anyhow!("There is no team {} in organisation {}", parent, org))
+}
@@ -0,0 +1,259 @@
+use super::common;
+use crate::filter::{Filter, Glob};
+use crate::git;
+use crate::path;
+use anyhow::{Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Row, Table};
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Search the content of all local repositories that match a pattern
+///
+/// By default this searches the files at HEAD. With --worktree it searches the working tree
+/// instead, skipping files ignored by .gitignore. Binary files are skipped.
+///
+/// Matches are printed as repo:path:line:text. Use --repo-regex to get a regex of the matched
+/// repositories that can be passed to the -r option of other commands.
+pub struct GrepArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    /// Regex to search for
+    pub pattern: String,
+    #[structopt(long, short)]
+    /// Option to search case insensitively
+    pub ignore_case: bool,
+    #[structopt(long, short)]
+    /// Option to search the working tree instead of HEAD
+    pub worktree: bool,
+    #[structopt(long, short, number_of_values = 1)]
+    /// Optional path globs to limit the search, ex: "*.mk" or "src/**/*.xml"
+    ///
+    /// A glob without / is matched against file names only. Repeat the option for more globs
+    pub path: Vec<Glob>,
+    #[structopt(long, short = "l")]
+    /// Only print the paths of matching files
+    pub files_with_matches: bool,
+    #[structopt(long)]
+    /// Only print a regex that matches the names of repositories with matches
+    pub repo_regex: bool,
+}
+
+impl GrepArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let pattern = RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.ignore_case)
+            .build()?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = sub_dirs
+            .par_iter()
+            .map(|d| self.grep(d, &pattern))
+            .collect();
+
+        if self.repo_regex {
+            let names: Vec<_> = statuses
+                .iter()
+                .filter(|s| s.match_count() > 0)
+                .map(|s| regex::escape(&s.repo))
+                .collect();
+            println!("^({})$", names.join("|"));
+            return Ok(());
+        }
+
+        for status in &statuses {
+            status.print(self.files_with_matches);
+        }
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+
+    fn grep(&self, dir: &PathBuf, pattern: &Regex) -> Status {
+        let mut dir_name = "".to_string();
+
+        let mut grep = || -> Result<Vec<FileMatch>> {
+            dir_name = path::dir_name(dir)?;
+            log::info!("Processing repo {}", dir_name);
+
+            let git_repo =
+                git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+            let mut files = vec![];
+            let search = |path: &str, content: &[u8]| {
+                if !self.path.is_empty() && !self.path.iter().any(|g| g.is_match(path)) {
+                    return;
+                }
+
+                let content = String::from_utf8_lossy(content);
+                let lines: Vec<_> = content
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| pattern.is_match(line))
+                    .map(|(i, line)| (i + 1, line.to_string()))
+                    .collect();
+
+                if !lines.is_empty() {
+                    files.push(FileMatch {
+                        path: path.to_string(),
+                        lines,
+                    });
+                }
+            };
+
+            if self.worktree {
+                git::for_each_worktree_file(&git_repo, search)?;
+            } else {
+                git::for_each_head_file(&git_repo, search)?;
+            }
+
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(files)
+        };
+
+        let result = grep();
+
+        Status {
+            repo: dir_name,
+            result,
+        }
+    }
+}
+
+struct FileMatch {
+    path: String,
+    lines: Vec<(usize, String)>,
+}
+
+struct Status {
+    repo: String,
+    result: Result<Vec<FileMatch>, Error>,
+}
+
+impl Status {
+    fn print(&self, files_only: bool) {
+        let files = match &self.result {
+            Ok(files) => files,
+            Err(_) => return,
+        };
+
+        for file in files {
+            if files_only {
+                println!("{}:{}", self.repo.magenta(), file.path.green());
+                continue;
+            }
+            for (number, line) in &file.lines {
+                println!(
+                    "{}:{}:{}:{}",
+                    self.repo.magenta(),
+                    file.path.green(),
+                    number.to_string().yellow(),
+                    line
+                );
+            }
+        }
+    }
+
+    fn file_count(&self) -> usize {
+        match &self.result {
+            Ok(files) => files.len(),
+            Err(_) => 0,
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        match &self.result {
+            Ok(files) => files.iter().map(|f| f.lines.len()).sum(),
+            Err(_) => 0,
+        }
+    }
+
+    fn to_row(&self) -> Row {
+        match &self.result {
+            Ok(_) => row!(
+                b -> &self.repo,
+                r -> self.match_count(),
+                r -> self.file_count()
+            ),
+            Err(_) => row!(b -> &self.repo, Frr -> "Failed", r -> "-"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn summarize(statuses: &[Status]) {
+    let rows: Vec<_> = statuses
+        .iter()
+        .filter(|s| s.match_count() > 0 || s.has_error())
+        .map(|s| s.to_row())
+        .collect();
+
+    let repos = statuses.iter().filter(|s| s.match_count() > 0).count();
+    if rows.is_empty() {
+        println!("\nThere is no match!");
+    } else {
+        let mut table = Table::init(rows);
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row!["Repo", "Matches", "Files"]);
+        println!();
+        table.printstd();
+
+        let msg = format!("\nFound matches in {} repos", repos);
+        println!("{}", msg.green());
+    }
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    if !errors.is_empty() {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
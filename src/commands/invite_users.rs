@@ -87,6 +87,7 @@ impl InviteUsersArgs {
     pub fn run(&self) -> Result<()> {
         let user_token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
 
         let emails: Vec<String> = self.emails.iter().map(|s| s.to_string()).collect();
         let teams = team_slug_to_ids(&organisation, &user_token, &self.teams)?;
@@ -0,0 +1,22 @@
+use super::issue_create::*;
+use super::issue_list::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create or list issues of all repositories that match a pattern
+pub enum IssueArgs {
+    #[structopt(name = "create")]
+    Create(IssueCreateArgs),
+    #[structopt(name = "list")]
+    List(IssueListArgs),
+}
+
+impl IssueArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            IssueArgs::Create(args) => args.run(),
+            IssueArgs::List(args) => args.run(),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use super::common;
+use super::models::Script;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::{CreateIssueBody, RemoteRepo};
+use anyhow::{anyhow, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create an issue in all repositories that match a pattern
+///
+/// Title and body can be produced per repository by --title-script and --body-script, they will
+/// override --title and --body.
+pub struct IssueCreateArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short, required_unless = "title-script")]
+    /// Title of the issues
+    pub title: Option<String>,
+    #[structopt(long)]
+    /// The script that will produce the title for each repository
+    pub title_script: Option<Script>,
+    #[structopt(long, short)]
+    /// Body of the issues
+    pub body: Option<String>,
+    #[structopt(long)]
+    /// The script that will produce the body for each repository
+    pub body_script: Option<Script>,
+    #[structopt(long, short, number_of_values = 1)]
+    /// Labels to add to the issues
+    pub label: Vec<String>,
+    #[structopt(long, short, number_of_values = 1)]
+    /// Users to assign the issues to
+    pub assignee: Vec<String>,
+    #[structopt(long, short)]
+    /// Title of the milestone to add the issues to
+    pub milestone: Option<String>,
+}
+
+impl IssueCreateArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| self.create(r, &user_token))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+
+    fn create(&self, repo: &RemoteRepo, token: &str) -> Status {
+        log::info!("Processing repo {}", repo.name);
+
+        let create = || -> Result<String> {
+            let milestone = match &self.milestone {
+                Some(title) => {
+                    let milestone = github::get_milestones(repo, token)?
+                        .into_iter()
+                        .find(|m| &m.title == title)
+                        .ok_or_else(|| anyhow!("There is no milestone {}", title))?;
+                    Some(milestone.number)
+                }
+                None => None,
+            };
+
+            let title = match &self.title_script {
+                Some(script) => script
+                    .execute_and_get_output(&repo.name, &repo.owner)?
+                    .trim()
+                    .to_string(),
+                None => self.title.clone().unwrap_or_default(),
+            };
+            let body = match &self.body_script {
+                Some(script) => Some(script.execute_and_get_output(&repo.name, &repo.owner)?),
+                None => self.body.clone(),
+            };
+
+            let issue = CreateIssueBody {
+                title,
+                body,
+                labels: self.label.clone(),
+                assignees: self.assignee.clone(),
+                milestone,
+            };
+            let issue = github::create_issue(repo, &issue, token)?;
+
+            Ok(issue.html_url)
+        };
+
+        Status {
+            repo: repo.name.clone(),
+            result: create(),
+        }
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<String, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.repo), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(url) => cell!(Fgl -> url),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Issue"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!(
+            "\nCreated issues in {} repos successfully!",
+            successes.len()
+        );
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -0,0 +1,99 @@
+use super::common;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::Issue;
+use anyhow::Result;
+use prettytable::{cell, format, row, Table};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// List open issues of all repositories that match a pattern
+///
+/// Issues are found with the GitHub search api, which returns at most 1000 issues
+pub struct IssueListArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short, number_of_values = 1)]
+    /// Only show issues with these labels
+    pub label: Vec<String>,
+    #[structopt(long, short)]
+    /// Only show issues assigned to this user
+    pub assignee: Option<String>,
+    #[structopt(long, short)]
+    /// Optional search query, ex: "keyboard in:title"
+    pub query: Option<String>,
+    #[structopt(long)]
+    /// Print issues as json
+    pub json: bool,
+}
+
+impl IssueListArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let mut query = format!("user:{} is:issue is:open", organisation);
+        for label in &self.label {
+            query = format!("{} label:\"{}\"", query, label);
+        }
+        if let Some(assignee) = &self.assignee {
+            query = format!("{} assignee:{}", query, assignee);
+        }
+        if let Some(q) = &self.query {
+            query = format!("{} {}", query, q);
+        }
+
+        let mut issues: Vec<_> = github::search_issues(&query, &user_token)?
+            .into_iter()
+            .filter(|i| match &self.regex {
+                Some(regex) => regex.is_match(i.repo_name()),
+                None => true,
+            })
+            .collect();
+        issues.sort_by(|a, b| {
+            a.repo_name()
+                .cmp(b.repo_name())
+                .then(a.number.cmp(&b.number))
+        });
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&issues)?);
+            return Ok(());
+        }
+
+        if issues.is_empty() {
+            println!("There is no open issues that matches {}", query);
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row!["Repo", "#", "Title", "Labels", "Assignees"]);
+        for issue in &issues {
+            table.add_row(to_row(issue));
+        }
+        table.printstd();
+
+        println!("\n{} open issues", issues.len());
+
+        Ok(())
+    }
+}
+
+fn to_row(issue: &Issue) -> prettytable::Row {
+    let labels: Vec<_> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+    let assignees: Vec<_> = issue.assignees.iter().map(|a| a.login.as_str()).collect();
+    row![
+        b -> issue.repo_name(),
+        r -> issue.number,
+        issue.title,
+        labels.join(", "),
+        assignees.join(", ")
+    ]
+}
@@ -0,0 +1,18 @@
+use super::label_sync::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Manage labels of all repositories that match a pattern
+pub enum LabelArgs {
+    #[structopt(name = "sync")]
+    Sync(LabelSyncArgs),
+}
+
+impl LabelArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            LabelArgs::Sync(args) => args.run(),
+        }
+    }
+}
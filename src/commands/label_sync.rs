@@ -0,0 +1,426 @@
+use super::common;
+use super::models::{LabelDefinition, LabelFile};
+use crate::filter::Filter;
+use crate::github;
+use crate::github::{Label, RemoteRepo};
+use anyhow::{Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Make labels of all repositories that match a pattern match a label file
+///
+/// The label file is a toml file with a list of labels:
+///
+/// [[labels]]
+/// name = "bug"
+/// color = "d73a4a"
+/// description = "Something isn't working"
+/// renamed_from = ["Bug", "type: bug"]
+///
+/// An existing label named in renamed_from is renamed instead of creating a new label, so that
+/// issues keep it. The changes are shown first and only applied after a confirmation.
+pub struct LabelSyncArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short, parse(from_os_str))]
+    /// Path to the label file
+    pub file: PathBuf,
+    #[structopt(long, short)]
+    /// Option to delete labels that are not in the file
+    pub prune: bool,
+}
+
+impl LabelSyncArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let label_file = LabelFile::get(&self.file)
+            .with_context(|| format!("Cannot read label file {:?}", self.file))?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let plans: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| Plan {
+                repo: r.clone(),
+                result: github::get_labels(r, &user_token)
+                    .map(|current| plan(&current, &label_file.labels, self.prune)),
+            })
+            .collect();
+
+        for plan in &plans {
+            plan.print();
+        }
+
+        let changes: Vec<_> = plans.iter().filter(|p| p.change_count() > 0).collect();
+        let errors: Vec<_> = plans.iter().filter(|p| p.result.is_err()).collect();
+
+        if !errors.is_empty() {
+            let msg = format!(
+                "There are {} repos that will be skipped because of errors:",
+                errors.len()
+            );
+            println!("\n{}\n", msg.red());
+
+            let mut error_table = Table::new();
+            error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+            error_table.set_titles(row!["Repo", "Error"]);
+            for error in errors {
+                if let Err(e) = &error.result {
+                    error_table.add_row(to_error_row(&error.repo.name, e));
+                }
+            }
+            error_table.printstd();
+        }
+
+        if changes.is_empty() {
+            println!("\nLabels of all repos are up to date!");
+            return Ok(());
+        }
+
+        let change_count: usize = changes.iter().map(|p| p.change_count()).sum();
+        if !confirm(change_count, changes.len())? {
+            println!("Command is aborted. Nothing is changed!");
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = changes.par_iter().map(|p| apply(p, &user_token)).collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum LabelChange {
+    Create(LabelDefinition),
+    /// Update name, colour or description of an existing label
+    Update {
+        from: String,
+        to: LabelDefinition,
+    },
+    Delete(String),
+}
+
+impl LabelChange {
+    fn describe(&self) -> String {
+        match self {
+            LabelChange::Create(label) => format!("+ {} (#{})", label.name, label.color),
+            LabelChange::Update { from, to } if from != &to.name => {
+                format!("~ {} -> {} (#{})", from, to.name, to.color)
+            }
+            LabelChange::Update { to, .. } => format!("~ {} (#{})", to.name, to.color),
+            LabelChange::Delete(name) => format!("- {}", name),
+        }
+    }
+}
+
+/// Changes to make the current labels of a repository match the definitions
+///
+/// Labels are matched by name first, and only the remaining ones by renamed_from, so a label is
+/// never used by two definitions
+fn plan(current: &[Label], definitions: &[LabelDefinition], prune: bool) -> Vec<LabelChange> {
+    let mut used = vec![false; current.len()];
+    let find = |name: &str, used: &mut [bool]| {
+        let index = current
+            .iter()
+            .enumerate()
+            .position(|(i, l)| !used[i] && l.name.to_lowercase() == name.to_lowercase());
+        if let Some(i) = index {
+            used[i] = true;
+        }
+        index
+    };
+
+    let mut matches: Vec<_> = definitions
+        .iter()
+        .map(|d| find(&d.name, &mut used))
+        .collect();
+    for (definition, index) in definitions.iter().zip(matches.iter_mut()) {
+        if index.is_none() {
+            *index = definition
+                .renamed_from
+                .iter()
+                .find_map(|old| find(old, &mut used));
+        }
+    }
+
+    let mut changes = vec![];
+
+    for (definition, index) in definitions.iter().zip(matches) {
+        match index {
+            Some(i) => {
+                let label = &current[i];
+                let description = label.description.as_deref().unwrap_or("");
+                if label.name != definition.name
+                    || label.color.to_lowercase() != definition.color.to_lowercase()
+                    || description != definition.description
+                {
+                    changes.push(LabelChange::Update {
+                        from: label.name.clone(),
+                        to: definition.clone(),
+                    });
+                }
+            }
+            None => changes.push(LabelChange::Create(definition.clone())),
+        }
+    }
+
+    if prune {
+        for (label, used) in current.iter().zip(used) {
+            if !used {
+                changes.push(LabelChange::Delete(label.name.clone()));
+            }
+        }
+    }
+
+    changes
+}
+
+fn apply(plan: &Plan, token: &str) -> Status {
+    log::info!("Processing repo {}", plan.repo.name);
+
+    let apply = || -> Result<usize> {
+        let changes = match &plan.result {
+            Ok(changes) => changes,
+            Err(_) => return Ok(0),
+        };
+
+        for change in changes {
+            match change {
+                LabelChange::Create(label) => github::create_label(
+                    &plan.repo,
+                    &label.name,
+                    &label.color,
+                    &label.description,
+                    token,
+                ),
+                LabelChange::Update { from, to } => github::update_label(
+                    &plan.repo,
+                    from,
+                    &to.name,
+                    &to.color,
+                    &to.description,
+                    token,
+                ),
+                LabelChange::Delete(name) => github::delete_label(&plan.repo, name, token),
+            }
+            .with_context(|| format!("Failed to apply {}", change.describe()))?;
+        }
+
+        Ok(changes.len())
+    };
+
+    Status {
+        repo: plan.repo.name.clone(),
+        result: apply(),
+    }
+}
+
+fn confirm(changes: usize, repos: usize) -> Result<bool> {
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to make {} label change(s) in {} repo(s)?\nEnter {} to continue",
+            changes, repos, key
+        ),
+        key,
+    )
+}
+
+struct Plan {
+    repo: RemoteRepo,
+    result: Result<Vec<LabelChange>, Error>,
+}
+
+impl Plan {
+    fn print(&self) {
+        let changes = match &self.result {
+            Ok(changes) if !changes.is_empty() => changes,
+            _ => return,
+        };
+
+        println!("{}", self.repo.name.bold());
+        for change in changes {
+            let line = change.describe();
+            match change {
+                LabelChange::Create(_) => println!("  {}", line.green()),
+                LabelChange::Update { .. } => println!("  {}", line.yellow()),
+                LabelChange::Delete(_) => println!("  {}", line.red()),
+            }
+        }
+        println!();
+    }
+
+    fn change_count(&self) -> usize {
+        match &self.result {
+            Ok(changes) => changes.len(),
+            Err(_) => 0,
+        }
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<usize, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.repo), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(count) => cell!(Fgl -> format!("{} changes", count)),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+}
+
+fn to_error_row(repo: &str, e: &Error) -> Row {
+    let msg = format!("{:?}", e);
+    let lines = common::sub_strings(msg.as_str(), 80);
+    let lines = lines.join("\n");
+    row!(cell!(b -> repo), cell!(Fr -> lines.as_str()))
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Result"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!("\nSynced labels of {} repos successfully!", successes.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            if let Err(e) = &error.result {
+                error_table.add_row(to_error_row(&error.repo, e));
+            }
+        }
+        error_table.printstd();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(name: &str, color: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: color.to_string(),
+            description: None,
+        }
+    }
+
+    fn definition(name: &str, color: &str, renamed_from: &[&str]) -> LabelDefinition {
+        LabelDefinition {
+            name: name.to_string(),
+            color: color.to_string(),
+            description: "".to_string(),
+            renamed_from: renamed_from.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_plan_create_update_rename() {
+        let current = vec![label("bug", "ffffff"), label("Feature", "000000")];
+        let definitions = vec![
+            definition("bug", "d73a4a", &[]),
+            definition("enhancement", "a2eeef", &["feature"]),
+            definition("docs", "0075ca", &[]),
+        ];
+
+        let changes = plan(&current, &definitions, false);
+
+        assert_eq!(
+            changes,
+            vec![
+                LabelChange::Update {
+                    from: "bug".to_string(),
+                    to: definitions[0].clone(),
+                },
+                LabelChange::Update {
+                    from: "Feature".to_string(),
+                    to: definitions[1].clone(),
+                },
+                LabelChange::Create(definitions[2].clone()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_rename_to_existing_name() {
+        let current = vec![label("question", "d876e3")];
+        let definitions = vec![
+            definition("help wanted", "008672", &["question"]),
+            definition("question", "d876e3", &[]),
+        ];
+
+        let changes = plan(&current, &definitions, false);
+
+        assert_eq!(changes, vec![LabelChange::Create(definitions[0].clone())]);
+    }
+
+    #[test]
+    fn test_plan_prune() {
+        let current = vec![label("bug", "d73a4a"), label("wontfix", "ffffff")];
+        let definitions = vec![definition("bug", "D73A4A", &[])];
+
+        assert_eq!(plan(&current, &definitions, false), vec![]);
+        assert_eq!(
+            plan(&current, &definitions, true),
+            vec![LabelChange::Delete("wontfix".to_string())]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use super::common;
+use crate::date;
+use crate::filter::Filter;
+use crate::git;
+use crate::path;
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use prettytable::{cell, format, row, Table};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show commits of all local repositories that match a pattern in one timeline
+///
+/// Commits reachable from HEAD of every repository are merged and sorted by commit time, newest
+/// first.
+pub struct LogArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Optional regex to filter commits by author name or email
+    pub author: Option<Filter>,
+    #[structopt(long)]
+    /// Only show commits since this date
+    ///
+    /// Date is in UTC and in the format YYYY-MM-DD, YYYY-MM-DDTHH:MM or YYYY-MM-DDTHH:MM:SS.
+    pub since: Option<String>,
+    #[structopt(long)]
+    /// Only show commits before this date
+    ///
+    /// Date is in UTC and in the format YYYY-MM-DD, YYYY-MM-DDTHH:MM or YYYY-MM-DDTHH:MM:SS.
+    pub until: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter commits by message
+    pub grep: Option<Filter>,
+    #[structopt(long, short)]
+    /// Only show commits that change files under this path
+    pub path: Option<String>,
+    #[structopt(long, short = "n")]
+    /// Maximum number of commits to show
+    pub limit: Option<usize>,
+    #[structopt(long)]
+    /// Print commits as json
+    pub json: bool,
+}
+
+#[derive(Serialize, Debug)]
+struct LogEntry {
+    repo: String,
+    sha: String,
+    author: String,
+    email: String,
+    time: i64,
+    date: String,
+    summary: String,
+}
+
+impl LogArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let since = parse_date(self.since.as_deref())?;
+        let until = parse_date(self.until.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let results: Vec<_> = sub_dirs
+            .par_iter()
+            .map(|d| self.log(d, since, until))
+            .collect();
+
+        let mut entries = vec![];
+        let mut errors = vec![];
+        for (repo, result) in results {
+            match result {
+                Ok(e) => entries.extend(e),
+                Err(e) => errors.push((repo, e)),
+            }
+        }
+
+        entries.sort_by_key(|e| Reverse(e.time));
+        if let Some(limit) = self.limit {
+            entries.truncate(limit);
+        }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+            for (repo, e) in &errors {
+                eprintln!("Failed to read log of {}: {:?}", repo, e);
+            }
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row!["Date", "Repo", "Commit", "Author", "Summary"]);
+        for entry in &entries {
+            table.add_row(row![
+                entry.date,
+                b -> entry.repo,
+                &entry.sha[..8],
+                entry.author,
+                entry.summary
+            ]);
+        }
+        table.printstd();
+
+        println!("\n{} commits", entries.len());
+
+        if !errors.is_empty() {
+            let msg = format!(
+                "There are {} errors when executing the command:",
+                errors.len()
+            );
+            println!("\n{}\n", msg.red());
+
+            let mut error_table = Table::new();
+            error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+            error_table.set_titles(row!["Repo", "Error"]);
+            for (repo, e) in errors {
+                let msg = format!("{:?}", e);
+                let lines = common::sub_strings(msg.as_str(), 80);
+                let lines = lines.join("\n");
+                error_table.add_row(row!(cell!(b -> repo), cell!(Fr -> lines.as_str())));
+            }
+            error_table.printstd();
+        }
+
+        Ok(())
+    }
+
+    fn log(
+        &self,
+        dir: &PathBuf,
+        since: Option<i64>,
+        until: Option<i64>,
+    ) -> (String, Result<Vec<LogEntry>>) {
+        let mut dir_name = "".to_string();
+
+        let mut log = || -> Result<Vec<LogEntry>> {
+            dir_name = path::dir_name(dir)?;
+            log::info!("Processing repo {}", dir_name);
+
+            let git_repo =
+                git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+            let mut entries = vec![];
+            for commit in git::log(&git_repo, since, until)? {
+                if let Some(author) = &self.author {
+                    if !author.is_match(&commit.author) && !author.is_match(&commit.email) {
+                        continue;
+                    }
+                }
+                if let Some(grep) = &self.grep {
+                    if !grep.is_match(&commit.message) {
+                        continue;
+                    }
+                }
+                if let Some(path) = &self.path {
+                    if !git::touches_path(&git_repo, commit.oid, path)? {
+                        continue;
+                    }
+                }
+
+                entries.push(LogEntry {
+                    repo: dir_name.clone(),
+                    sha: commit.oid.to_string(),
+                    author: commit.author,
+                    email: commit.email,
+                    time: commit.time,
+                    date: date::format_timestamp(commit.time),
+                    summary: commit.message.lines().next().unwrap_or("").to_string(),
+                });
+            }
+
+            Ok(entries)
+        };
+
+        let result = log();
+        (dir_name, result)
+    }
+}
+
+fn parse_date(date: Option<&str>) -> Result<Option<i64>> {
+    match date {
+        Some(d) => date::parse_timestamp(d)
+            .map(Some)
+            .ok_or_else(|| anyhow!("{} is not a valid date", d)),
+        None => Ok(None),
+    }
+}
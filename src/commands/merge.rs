@@ -1,8 +1,9 @@
 use super::common;
+use super::resume::{resume, Resume};
 use crate::filter::Filter;
 use crate::git;
 use crate::git::MergeStatus;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -17,12 +18,18 @@ pub struct MergeArgs {
     #[structopt(long, short)]
     /// Optional regex to filter repositories
     pub regex: Option<Filter>,
-    #[structopt(long, short)]
+    #[structopt(long, short, required_unless_one(&["resume", "abort"]))]
     /// The branch to be merged
-    pub branch: String,
+    pub branch: Option<String>,
     #[structopt(long, short)]
     /// Option to abort merging process if there is a conflict
     pub abort_if_conflict: bool,
+    #[structopt(long = "continue", conflicts_with = "abort")]
+    /// Continue unfinished merges after the conflicts are resolved and added
+    pub resume: bool,
+    #[structopt(long)]
+    /// Abort unfinished merges
+    pub abort: bool,
 }
 
 impl MergeArgs {
@@ -30,10 +37,22 @@ impl MergeArgs {
         let root = common::root()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
 
+        if self.resume {
+            return resume(&organisation, self.regex.as_ref(), Resume::Continue, false);
+        }
+        if self.abort {
+            return resume(&organisation, self.regex.as_ref(), Resume::Abort, false);
+        }
+
+        let branch = self
+            .branch
+            .as_deref()
+            .ok_or_else(|| anyhow!("Branch name is required"))?;
+
         let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
 
         for dir in sub_dirs {
-            match merge(&dir, &self.branch, self.abort_if_conflict) {
+            match merge(&dir, branch, self.abort_if_conflict) {
                 Ok(status) => match status {
                     MergeStatus::FastForward => println!("Merge fast forward"),
                     MergeStatus::NormalMerge => println!("Merge made by the 'recursive' strategy"),
@@ -47,7 +66,7 @@ impl MergeArgs {
                 },
                 Err(e) => println!(
                     "Failed to merge branch {} for dir {:?} because {:?}",
-                    self.branch, dir, e
+                    branch, dir, e
                 ),
             }
         }
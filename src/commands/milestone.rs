@@ -0,0 +1,36 @@
+use super::milestone_close::*;
+use super::milestone_create::*;
+use super::milestone_list::*;
+use crate::github;
+use crate::github::{Milestone, RemoteRepo};
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create, close or list milestones of all repositories that match a pattern
+pub enum MilestoneArgs {
+    #[structopt(name = "close")]
+    Close(MilestoneCloseArgs),
+    #[structopt(name = "create")]
+    Create(MilestoneCreateArgs),
+    #[structopt(name = "list")]
+    List(MilestoneListArgs),
+}
+
+impl MilestoneArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            MilestoneArgs::Close(args) => args.run(),
+            MilestoneArgs::Create(args) => args.run(),
+            MilestoneArgs::List(args) => args.run(),
+        }
+    }
+}
+
+/// Find a milestone of a repository by its title
+pub fn find_milestone(repo: &RemoteRepo, title: &str, token: &str) -> Result<Option<Milestone>> {
+    let milestone = github::get_milestones(repo, token)?
+        .into_iter()
+        .find(|m| m.title == title);
+    Ok(milestone)
+}
@@ -0,0 +1,181 @@
+use super::common;
+use super::milestone::find_milestone;
+use crate::date;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::RemoteRepo;
+use anyhow::{anyhow, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create a milestone in all repositories that match a pattern
+///
+/// Repositories that already have a milestone with the same title are skipped
+pub struct MilestoneCreateArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Title of the milestone
+    pub title: String,
+    #[structopt(long, short)]
+    /// Optional description of the milestone
+    pub description: Option<String>,
+    #[structopt(long)]
+    /// Optional due date of the milestone
+    ///
+    /// Date is in UTC and in the format YYYY-MM-DD, YYYY-MM-DDTHH:MM or YYYY-MM-DDTHH:MM:SS.
+    pub due: Option<String>,
+}
+
+impl MilestoneCreateArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let due_on = match &self.due {
+            Some(due) => {
+                let time = date::parse_timestamp(due)
+                    .ok_or_else(|| anyhow!("{} is not a valid date", due))?;
+                Some(date::format_iso_timestamp(time))
+            }
+            None => None,
+        };
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| self.create(r, due_on.as_deref(), &user_token))
+            .collect();
+
+        summarize(&statuses, &self.title);
+
+        Ok(())
+    }
+
+    fn create(&self, repo: &RemoteRepo, due_on: Option<&str>, token: &str) -> Status {
+        log::info!("Processing repo {}", repo.name);
+
+        let create = || -> Result<bool> {
+            if find_milestone(repo, &self.title, token)?.is_some() {
+                return Ok(false);
+            }
+
+            github::create_milestone(
+                repo,
+                &self.title,
+                self.description.as_deref(),
+                due_on,
+                token,
+            )?;
+            Ok(true)
+        };
+
+        Status {
+            repo: repo.name.clone(),
+            result: create(),
+        }
+    }
+}
+
+struct Status {
+    repo: String,
+    /// false if the milestone already exists
+    result: Result<bool, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.repo), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(true) => cell!(Fgl -> "Created"),
+            Ok(false) => cell!(l -> "Already exists"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn is_created(&self) -> bool {
+        matches!(self.result, Ok(true))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Milestone"]);
+    table
+}
+
+fn summarize(statuses: &[Status], title: &str) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let created: Vec<_> = statuses.iter().filter(|s| s.is_created()).collect();
+
+    if !created.is_empty() {
+        let msg = format!(
+            "\nCreated milestone {} in {} repos successfully!",
+            title,
+            created.len()
+        );
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
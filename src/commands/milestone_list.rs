@@ -0,0 +1,147 @@
+use super::common;
+use super::milestone::find_milestone;
+use crate::filter::Filter;
+use crate::github::Milestone;
+use anyhow::{Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Row, Table};
+use rayon::prelude::*;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show open and closed issue counts of a milestone in all repositories that match a pattern
+pub struct MilestoneListArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Title of the milestone
+    pub title: String,
+}
+
+impl MilestoneListArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| Status {
+                repo: r.name.clone(),
+                result: find_milestone(r, &self.title, &user_token),
+            })
+            .collect();
+
+        summarize(&statuses, &self.title);
+
+        Ok(())
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<Option<Milestone>, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Option<Row> {
+        let milestone = match &self.result {
+            Ok(Some(milestone)) => milestone,
+            _ => return None,
+        };
+
+        let total = milestone.open_issues + milestone.closed_issues;
+        let progress = (milestone.closed_issues * 100)
+            .checked_div(total)
+            .map(|p| format!("{}%", p))
+            .unwrap_or_else(|| "-".to_string());
+        let due = milestone
+            .due_on
+            .as_deref()
+            .and_then(|d| d.get(..10))
+            .unwrap_or("-");
+
+        Some(row!(
+            b -> &self.repo,
+            milestone.state,
+            due,
+            r -> milestone.open_issues,
+            r -> milestone.closed_issues,
+            r -> progress
+        ))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn summarize(statuses: &[Status], title: &str) {
+    let rows: Vec<_> = statuses.iter().filter_map(|s| s.to_row()).collect();
+
+    if rows.is_empty() {
+        println!("There is no repositories with milestone {}", title);
+    } else {
+        let count = rows.len();
+        let mut table = Table::init(rows);
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row!["Repo", "State", "Due", "Open", "Closed", "Progress"]);
+        table.printstd();
+
+        let open: usize = statuses
+            .iter()
+            .filter_map(|s| s.result.as_ref().ok().and_then(|m| m.as_ref()))
+            .map(|m| m.open_issues)
+            .sum();
+        println!(
+            "\n{} repos have milestone {} with {} open issues in total",
+            count, title, open
+        );
+    }
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    if !errors.is_empty() {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -2,6 +2,7 @@ pub mod add;
 pub mod add_repos;
 pub mod add_users;
 pub mod apply;
+pub mod apply_config;
 pub mod branch;
 pub mod branch_default;
 pub mod branch_protect;
@@ -18,32 +19,72 @@ pub mod create_discussion;
 pub mod create_repo;
 pub mod create_team;
 pub mod fetch;
+pub mod grep;
 pub mod hook;
 pub mod hook_create;
 pub mod hook_delete;
 pub mod init_config;
 pub mod invite;
 pub mod invite_users;
+pub mod issue;
+pub mod issue_create;
+pub mod issue_list;
+pub mod label;
+pub mod label_sync;
+pub mod log;
 pub mod make;
 pub mod merge;
+pub mod milestone;
+pub mod milestone_close;
+pub mod milestone_create;
+pub mod milestone_list;
 pub mod models;
 pub mod patterns;
+pub mod plan;
+pub mod pr;
+pub mod pr_create;
+pub mod pr_list;
+pub mod pr_merge;
 pub mod pull;
 pub mod push;
+pub mod release;
+pub mod release_create;
 pub mod remove;
 pub mod remove_repos;
 pub mod remove_users;
 pub mod rename;
+pub mod replace;
+pub mod resume;
 pub mod set;
 pub mod set_default_organisation;
 pub mod set_info;
 pub mod set_secret;
+pub mod set_settings;
 pub mod set_team_permission;
 pub mod show;
+pub mod show_access;
 pub mod show_config;
 pub mod show_repos;
+pub mod show_settings;
+pub mod show_teams;
 pub mod show_users;
+pub mod snapshot;
+pub mod snapshot_restore;
+pub mod snapshot_save;
+pub mod stash;
+pub mod stash_drop;
+pub mod stash_list;
+pub mod stash_pop;
 pub mod status;
+pub mod sync;
+pub mod tag;
+pub mod tag_create;
+pub mod tag_delete;
+pub mod tag_list;
+pub mod tag_push;
+pub mod team;
+pub mod team_helper;
+pub mod team_sync;
 pub mod template;
 pub mod topic;
 pub mod topic_add;
@@ -57,6 +98,7 @@ pub mod workflow_run;
 
 pub use add::*;
 pub use apply::*;
+pub use apply_config::*;
 pub use branch::*;
 pub use checkout::*;
 pub use ci::*;
@@ -65,20 +107,34 @@ pub use clone::*;
 pub use commit::*;
 pub use create::*;
 pub use fetch::*;
+pub use grep::*;
 pub use hook::*;
 pub use init_config::*;
 pub use invite::*;
+pub use issue::*;
+pub use label::*;
+pub use log::*;
 pub use make::*;
 pub use merge::*;
+pub use milestone::*;
 pub use models::*;
+pub use plan::*;
+pub use pr::*;
 pub use pull::*;
 pub use push::*;
+pub use release::*;
 pub use remove::*;
 pub use remove_repos::*;
 pub use rename::*;
+pub use replace::*;
 pub use set::*;
 pub use show::*;
+pub use snapshot::*;
+pub use stash::*;
 pub use status::*;
+pub use sync::*;
+pub use tag::*;
+pub use team::*;
 pub use template::*;
 pub use topic::*;
 pub use transfer::*;
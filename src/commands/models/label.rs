@@ -0,0 +1,29 @@
+use crate::toml::read_file;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Label definitions that repositories should match
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LabelFile {
+    pub labels: Vec<LabelDefinition>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LabelDefinition {
+    pub name: String,
+    /// Hex colour without the leading #
+    pub color: String,
+    #[serde(default)]
+    pub description: String,
+    /// Old names of this label, an existing label with one of these names is renamed so that
+    /// issues keep it
+    #[serde(default)]
+    pub renamed_from: Vec<String>,
+}
+
+impl LabelFile {
+    pub fn get(path: &PathBuf) -> Result<LabelFile> {
+        read_file(path)
+    }
+}
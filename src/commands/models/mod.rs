@@ -1,9 +1,17 @@
 pub mod exist_directory;
+pub mod label;
+pub mod org_config;
 pub mod root_directory;
 pub mod script;
+pub mod snapshot;
+pub mod team;
 pub mod template;
 
 pub use exist_directory::*;
+pub use label::*;
+pub use org_config::*;
 pub use root_directory::*;
 pub use script::*;
+pub use snapshot::*;
+pub use team::*;
 pub use template::*;
@@ -0,0 +1,53 @@
+use crate::toml::read_file;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The repositories and teams that an organisation is expected to have
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct OrgConfig {
+    #[serde(default)]
+    pub repos: Vec<RepoConfig>,
+    #[serde(default)]
+    pub teams: Vec<TeamConfig>,
+}
+
+/// Settings that are not given are left as they are
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RepoConfig {
+    pub name: String,
+    pub public: Option<bool>,
+    pub description: Option<String>,
+    pub topics: Option<Vec<String>>,
+    pub default_branch: Option<String>,
+    /// Branches that must be protected, protection of other branches is left as it is
+    #[serde(default)]
+    pub protected_branches: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TeamConfig {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub secret: bool,
+    /// Members of the team, members that are not listed here or in maintainers are removed
+    #[serde(default)]
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub maintainers: Vec<String>,
+    /// Permission of the team for each repository: pull, triage, push, maintain or admin
+    #[serde(default)]
+    pub repos: BTreeMap<String, String>,
+}
+
+impl OrgConfig {
+    pub fn get(path: &PathBuf) -> Result<OrgConfig> {
+        read_file(path)
+    }
+}
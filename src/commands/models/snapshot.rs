@@ -0,0 +1,62 @@
+use crate::filter::{Filter, Filterable};
+use crate::git;
+use crate::toml::{read_file, write_to_file};
+use anyhow::Result;
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Lock file with the exact commit of every repository in an organisation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    pub organisation: String,
+    pub repos: Vec<RepoSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RepoSnapshot {
+    pub name: String,
+    /// Branch at the time of the snapshot, None if HEAD was detached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    pub sha: String,
+    /// Url of origin, used to clone the repository if it is missing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Snapshot {
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        write_to_file(path, self)
+    }
+
+    pub fn get(path: &PathBuf) -> Result<Snapshot> {
+        read_file(path)
+    }
+}
+
+impl RepoSnapshot {
+    /// Record the current branch and HEAD commit of a repository
+    pub fn from_repo(name: &str, repo: &Repository) -> Result<RepoSnapshot> {
+        let sha = git::head_sha(repo)?;
+        let branch = if repo.head_detached()? {
+            None
+        } else {
+            Some(git::head_shorthand(repo)?)
+        };
+        let url = git::remote_url(repo, "origin")?;
+
+        Ok(RepoSnapshot {
+            name: name.to_string(),
+            branch,
+            sha,
+            url,
+        })
+    }
+}
+
+impl Filterable for RepoSnapshot {
+    fn is_match(&self, filter: &Filter) -> bool {
+        filter.is_match(&self.name)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::toml::read_file;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Members and maintainers that teams should have
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TeamFile {
+    pub teams: Vec<TeamMembers>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TeamMembers {
+    /// Name or slug of the team
+    pub name: String,
+    #[serde(default)]
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub maintainers: Vec<String>,
+}
+
+impl TeamFile {
+    pub fn get(path: &PathBuf) -> Result<TeamFile> {
+        read_file(path)
+    }
+}
@@ -0,0 +1,564 @@
+use super::common;
+use super::models::{OrgConfig, RepoConfig, TeamConfig};
+use super::team_helper::{plan_members, MemberChange, TeamMembership};
+use crate::github;
+use crate::github::RemoteRepo;
+use anyhow::Result;
+use colored::*;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show the changes needed to make an organisation match an organisation file
+///
+/// The file describes the expected repositories (visibility, description, topics, default
+/// branch and protected branches) and teams (members and repository permissions).
+/// Nothing is changed, use apply-config to make the changes.
+pub struct PlanArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short, default_value = "org.toml")]
+    /// Path to the organisation file
+    pub file: PathBuf,
+}
+
+impl PlanArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+        let config = OrgConfig::get(&self.file)?;
+
+        let state = OrgState::fetch(&organisation, &config, &user_token)?;
+        let changes = plan(&config, &state);
+
+        print_plan(&organisation, &config, &state, &changes);
+
+        Ok(())
+    }
+}
+
+/// The current state of the repositories and teams that are listed in an organisation file
+pub struct OrgState {
+    pub repos: BTreeMap<String, RepoState>,
+    /// Keyed by the team name of the organisation file
+    pub teams: BTreeMap<String, TeamState>,
+}
+
+pub struct RepoState {
+    pub remote: RemoteRepo,
+    pub public: bool,
+    pub description: String,
+    pub topics: Vec<String>,
+    /// Protected branches among the ones listed in the organisation file
+    pub protected_branches: Vec<String>,
+}
+
+pub struct TeamState {
+    pub slug: String,
+    pub membership: TeamMembership,
+    /// Repository name to permission
+    pub repos: BTreeMap<String, String>,
+}
+
+impl OrgState {
+    pub fn fetch(org: &str, config: &OrgConfig, token: &str) -> Result<OrgState> {
+        let remote_repos = github::list_org_repos(token, org)?;
+
+        let repos: Result<Vec<_>> = config
+            .repos
+            .par_iter()
+            .filter_map(|c| {
+                let remote = remote_repos.iter().find(|r| r.name == c.name)?;
+                Some(fetch_repo(remote, c, token).map(|s| (c.name.clone(), s)))
+            })
+            .collect();
+
+        let teams = github::get_teams(org, token)?;
+        let teams: Result<Vec<_>> = config
+            .teams
+            .par_iter()
+            .filter_map(|c| {
+                let team = teams
+                    .iter()
+                    .find(|t| t.name == c.name || t.slug == c.name)?;
+                Some(fetch_team(org, &team.slug, token).map(|s| (c.name.clone(), s)))
+            })
+            .collect();
+
+        Ok(OrgState {
+            repos: repos?.into_iter().collect(),
+            teams: teams?.into_iter().collect(),
+        })
+    }
+}
+
+fn fetch_repo(remote: &RemoteRepo, config: &RepoConfig, token: &str) -> Result<RepoState> {
+    log::info!("Fetching repo {}", remote.name);
+
+    let repo = github::get_repo(&remote.owner, &remote.name, token)?;
+    let topics = github::get_topics(remote, token)?;
+
+    let mut protected_branches = vec![];
+    for branch in &config.protected_branches {
+        if github::is_branch_protected(remote, branch, token)? {
+            protected_branches.push(branch.clone());
+        }
+    }
+
+    Ok(RepoState {
+        remote: remote.clone(),
+        public: !repo.private,
+        description: repo.description.unwrap_or_default(),
+        topics,
+        protected_branches,
+    })
+}
+
+fn fetch_team(org: &str, slug: &str, token: &str) -> Result<TeamState> {
+    log::info!("Fetching team {}", slug);
+
+    let membership = TeamMembership::fetch(org, slug, token)?;
+    let repos = github::get_team_repos(org, slug, token)?
+        .iter()
+        .map(|r| (r.name.clone(), r.permission().to_string()))
+        .collect();
+
+    Ok(TeamState {
+        slug: slug.to_string(),
+        membership,
+        repos,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    CreateRepo {
+        repo: String,
+        public: bool,
+    },
+    SetVisibility {
+        repo: String,
+        public: bool,
+    },
+    SetDescription {
+        repo: String,
+        description: String,
+    },
+    SetTopics {
+        repo: String,
+        topics: Vec<String>,
+    },
+    SetDefaultBranch {
+        repo: String,
+        branch: String,
+    },
+    /// Creates the team with its maintainers, `members` are all users of the team
+    CreateTeam {
+        team: String,
+        description: String,
+        secret: bool,
+        maintainers: Vec<String>,
+        members: Vec<String>,
+    },
+    Member {
+        team: String,
+        change: MemberChange,
+    },
+    SetPermission {
+        team: String,
+        repo: String,
+        permission: String,
+    },
+    ProtectBranch {
+        repo: String,
+        branch: String,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::CreateRepo { repo, public } => write!(
+                f,
+                "+ create {} repo {}",
+                if *public { "public" } else { "private" },
+                repo
+            ),
+            Change::SetVisibility { repo, public } => write!(
+                f,
+                "~ make repo {} {}",
+                repo,
+                if *public { "public" } else { "private" }
+            ),
+            Change::SetDescription { repo, description } => {
+                write!(f, "~ set description of {} to {:?}", repo, description)
+            }
+            Change::SetTopics { repo, topics } => {
+                write!(f, "~ set topics of {} to [{}]", repo, topics.join(", "))
+            }
+            Change::SetDefaultBranch { repo, branch } => {
+                write!(f, "~ set default branch of {} to {}", repo, branch)
+            }
+            Change::CreateTeam { team, secret, .. } => write!(
+                f,
+                "+ create {} team {}",
+                if *secret { "secret" } else { "visible" },
+                team
+            ),
+            Change::Member { team, change } => match change {
+                MemberChange::Add { user, role } => {
+                    write!(f, "+ add {} to team {} as {}", user, team, role)
+                }
+                MemberChange::SetRole { user, role } => {
+                    write!(f, "~ make {} a {} of team {}", user, role, team)
+                }
+                MemberChange::Remove(user) => write!(f, "- remove {} from team {}", user, team),
+            },
+            Change::SetPermission {
+                team,
+                repo,
+                permission,
+            } => write!(f, "~ give team {} {} access to {}", team, permission, repo),
+            Change::ProtectBranch { repo, branch } => {
+                write!(f, "+ protect branch {} of {}", branch, repo)
+            }
+        }
+    }
+}
+
+/// Changes needed to go from `state` to `config`, in the order they can be applied
+///
+/// Repositories come first so that teams can be given access to them. Default branches and
+/// branch protection of repositories that don't exist yet are left out, since a new
+/// repository has no branches until something is pushed to it.
+pub fn plan(config: &OrgConfig, state: &OrgState) -> Vec<Change> {
+    let mut changes = vec![];
+
+    for repo in &config.repos {
+        plan_repo(repo, state.repos.get(&repo.name), &mut changes);
+    }
+
+    for team in &config.teams {
+        plan_team(team, state.teams.get(&team.name), &mut changes);
+    }
+
+    for repo in &config.repos {
+        let current = match state.repos.get(&repo.name) {
+            Some(current) => current,
+            None => continue,
+        };
+        for branch in &repo.protected_branches {
+            if !current.protected_branches.contains(branch) {
+                changes.push(Change::ProtectBranch {
+                    repo: repo.name.clone(),
+                    branch: branch.clone(),
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+fn plan_repo(config: &RepoConfig, current: Option<&RepoState>, changes: &mut Vec<Change>) {
+    let repo = config.name.clone();
+
+    match current {
+        None => changes.push(Change::CreateRepo {
+            repo: repo.clone(),
+            public: config.public.unwrap_or(false),
+        }),
+        Some(current) => {
+            if let Some(public) = config.public {
+                if public != current.public {
+                    changes.push(Change::SetVisibility {
+                        repo: repo.clone(),
+                        public,
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(description) = &config.description {
+        if current.map_or(!description.is_empty(), |c| &c.description != description) {
+            changes.push(Change::SetDescription {
+                repo: repo.clone(),
+                description: description.clone(),
+            });
+        }
+    }
+
+    if let Some(topics) = &config.topics {
+        let mut topics = topics.clone();
+        topics.sort();
+        let mut current_topics = current.map(|c| c.topics.clone()).unwrap_or_default();
+        current_topics.sort();
+        if topics != current_topics {
+            changes.push(Change::SetTopics {
+                repo: repo.clone(),
+                topics,
+            });
+        }
+    }
+
+    if let (Some(branch), Some(current)) = (&config.default_branch, current) {
+        if current.remote.default_branch.as_ref() != Some(branch) {
+            changes.push(Change::SetDefaultBranch {
+                repo,
+                branch: branch.clone(),
+            });
+        }
+    }
+}
+
+fn plan_team(config: &TeamConfig, current: Option<&TeamState>, changes: &mut Vec<Change>) {
+    let team = config.name.clone();
+
+    // A new team is created with its maintainers, so only the other members are left to add
+    let created;
+    let membership = match current {
+        Some(current) => &current.membership,
+        None => {
+            changes.push(Change::CreateTeam {
+                team: team.clone(),
+                description: config.description.clone(),
+                secret: config.secret,
+                maintainers: config.maintainers.clone(),
+                members: config.members.clone(),
+            });
+            created = TeamMembership {
+                members: vec![],
+                maintainers: config.maintainers.clone(),
+            };
+            &created
+        }
+    };
+
+    for change in plan_members(membership, &config.members, &config.maintainers) {
+        changes.push(Change::Member {
+            team: team.clone(),
+            change,
+        });
+    }
+
+    for (repo, permission) in &config.repos {
+        if current.and_then(|c| c.repos.get(repo)) != Some(permission) {
+            changes.push(Change::SetPermission {
+                team: team.clone(),
+                repo: repo.clone(),
+                permission: permission.clone(),
+            });
+        }
+    }
+}
+
+pub fn print_plan(org: &str, config: &OrgConfig, state: &OrgState, changes: &[Change]) {
+    if changes.is_empty() {
+        println!("Organisation {} matches the organisation file!", org);
+        return;
+    }
+
+    println!("Changes for organisation {}:\n", org);
+    for change in changes {
+        let line = change.to_string();
+        let line = match change {
+            Change::Member {
+                change: MemberChange::Remove(_),
+                ..
+            } => line.red(),
+            Change::CreateRepo { .. }
+            | Change::CreateTeam { .. }
+            | Change::Member {
+                change: MemberChange::Add { .. },
+                ..
+            }
+            | Change::ProtectBranch { .. } => line.green(),
+            _ => line.yellow(),
+        };
+        println!("{}", line);
+    }
+
+    let new_repos: Vec<_> = config
+        .repos
+        .iter()
+        .filter(|r| !state.repos.contains_key(&r.name))
+        .filter(|r| r.default_branch.is_some() || !r.protected_branches.is_empty())
+        .map(|r| r.name.as_str())
+        .collect();
+    if !new_repos.is_empty() {
+        println!(
+            "\nDefault branch and branch protection of {} will be planned once they have been pushed to",
+            new_repos.join(", ")
+        );
+    }
+
+    println!("\n{} change(s) in total", changes.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_repos() {
+        let config = OrgConfig {
+            repos: vec![
+                RepoConfig {
+                    name: "lang-sme".to_string(),
+                    public: Some(true),
+                    description: Some("North Sami".to_string()),
+                    topics: Some(vec!["lang".to_string(), "sme".to_string()]),
+                    default_branch: Some("main".to_string()),
+                    protected_branches: vec!["main".to_string()],
+                },
+                RepoConfig {
+                    name: "lang-new".to_string(),
+                    default_branch: Some("main".to_string()),
+                    protected_branches: vec!["main".to_string()],
+                    ..RepoConfig::default()
+                },
+            ],
+            teams: vec![],
+        };
+        let mut repos = BTreeMap::new();
+        repos.insert(
+            "lang-sme".to_string(),
+            RepoState {
+                remote: RemoteRepo {
+                    name: "lang-sme".to_string(),
+                    owner: "org".to_string(),
+                    ssh_url: String::new(),
+                    https_url: String::new(),
+                    default_branch: Some("master".to_string()),
+                },
+                public: true,
+                description: "North Sami".to_string(),
+                topics: vec!["sme".to_string(), "lang".to_string()],
+                protected_branches: vec![],
+            },
+        );
+        let state = OrgState {
+            repos,
+            teams: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            plan(&config, &state),
+            vec![
+                Change::SetDefaultBranch {
+                    repo: "lang-sme".to_string(),
+                    branch: "main".to_string()
+                },
+                Change::CreateRepo {
+                    repo: "lang-new".to_string(),
+                    public: false
+                },
+                Change::ProtectBranch {
+                    repo: "lang-sme".to_string(),
+                    branch: "main".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_teams() {
+        let mut team_repos = BTreeMap::new();
+        team_repos.insert("lang-sme".to_string(), "push".to_string());
+        team_repos.insert("lang-sma".to_string(), "admin".to_string());
+        let config = OrgConfig {
+            repos: vec![],
+            teams: vec![TeamConfig {
+                name: "Linguists".to_string(),
+                members: vec!["anna".to_string(), "bo".to_string()],
+                maintainers: vec!["bo".to_string()],
+                repos: team_repos,
+                ..TeamConfig::default()
+            }],
+        };
+        let mut current_repos = BTreeMap::new();
+        current_repos.insert("lang-sme".to_string(), "push".to_string());
+        let mut teams = BTreeMap::new();
+        teams.insert(
+            "Linguists".to_string(),
+            TeamState {
+                slug: "linguists".to_string(),
+                membership: TeamMembership {
+                    members: vec!["Anna".to_string(), "bo".to_string(), "cecilie".to_string()],
+                    maintainers: vec![],
+                },
+                repos: current_repos,
+            },
+        );
+        let state = OrgState {
+            repos: BTreeMap::new(),
+            teams,
+        };
+
+        assert_eq!(
+            plan(&config, &state),
+            vec![
+                Change::Member {
+                    team: "Linguists".to_string(),
+                    change: MemberChange::SetRole {
+                        user: "bo".to_string(),
+                        role: "maintainer".to_string()
+                    }
+                },
+                Change::Member {
+                    team: "Linguists".to_string(),
+                    change: MemberChange::Remove("cecilie".to_string())
+                },
+                Change::SetPermission {
+                    team: "Linguists".to_string(),
+                    repo: "lang-sma".to_string(),
+                    permission: "admin".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_new_team() {
+        let config = OrgConfig {
+            repos: vec![],
+            teams: vec![TeamConfig {
+                name: "Translators".to_string(),
+                members: vec!["anna".to_string(), "bo".to_string()],
+                maintainers: vec!["bo".to_string()],
+                ..TeamConfig::default()
+            }],
+        };
+        let state = OrgState {
+            repos: BTreeMap::new(),
+            teams: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            plan(&config, &state),
+            vec![
+                Change::CreateTeam {
+                    team: "Translators".to_string(),
+                    description: String::new(),
+                    secret: false,
+                    maintainers: vec!["bo".to_string()],
+                    members: vec!["anna".to_string(), "bo".to_string()],
+                },
+                Change::Member {
+                    team: "Translators".to_string(),
+                    change: MemberChange::Add {
+                        user: "anna".to_string(),
+                        role: "member".to_string()
+                    }
+                },
+            ]
+        );
+    }
+}
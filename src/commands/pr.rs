@@ -0,0 +1,26 @@
+use super::pr_create::*;
+use super::pr_list::*;
+use super::pr_merge::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Manage pull requests of all repositories that match a pattern
+pub enum PrArgs {
+    #[structopt(name = "create")]
+    Create(PrCreateArgs),
+    #[structopt(name = "list")]
+    List(PrListArgs),
+    #[structopt(name = "merge")]
+    Merge(PrMergeArgs),
+}
+
+impl PrArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            PrArgs::Create(args) => args.run(),
+            PrArgs::List(args) => args.run(),
+            PrArgs::Merge(args) => args.run(),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use super::common;
+use super::models::Script;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::{CreatePullRequestBody, PullRequest, RemoteRepo};
+use anyhow::{anyhow, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Open a pull request for a branch in all repositories that match a pattern
+///
+/// Repositories where the branch doesn't exist, has no commits ahead of the base branch or
+/// already has an open pull request are skipped. Reviewers and labels are still added to an
+/// open pull request, so running the command again adds the ones that failed the first time.
+///
+/// Title and body can be produced per repository by --title-script and --body-script, they will
+/// override --title and --body.
+pub struct PrCreateArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long)]
+    /// The branch that contains the changes
+    pub head: String,
+    #[structopt(long)]
+    /// The branch to merge into, default is the default branch of each repository
+    pub base: Option<String>,
+    #[structopt(long, short, required_unless = "title-script")]
+    /// Title of the pull requests
+    pub title: Option<String>,
+    #[structopt(long)]
+    /// The script that will produce the title for each repository
+    pub title_script: Option<Script>,
+    #[structopt(long, short)]
+    /// Body of the pull requests
+    pub body: Option<String>,
+    #[structopt(long)]
+    /// The script that will produce the body for each repository
+    pub body_script: Option<Script>,
+    #[structopt(long, number_of_values = 1)]
+    /// Users to request reviews from
+    pub reviewer: Vec<String>,
+    #[structopt(long, number_of_values = 1)]
+    /// Team slugs to request reviews from
+    pub team_reviewer: Vec<String>,
+    #[structopt(long, short, number_of_values = 1)]
+    /// Labels to add to the pull requests
+    pub label: Vec<String>,
+    #[structopt(long, short)]
+    /// Option to open draft pull requests
+    pub draft: bool,
+}
+
+impl PrCreateArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| self.create(r, &user_token))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+
+    fn create(&self, repo: &RemoteRepo, token: &str) -> Status {
+        log::info!("Processing repo {}", repo.name);
+
+        let create = || -> Result<Outcome> {
+            let base = match (&self.base, &repo.default_branch) {
+                (Some(base), _) => base.to_string(),
+                (None, Some(default)) => default.to_string(),
+                (None, None) => {
+                    return Err(anyhow!(
+                        "Repository has no default branch, please provide --base"
+                    ))
+                }
+            };
+
+            let ahead = match github::compare_branches(repo, &base, &self.head, token)? {
+                Some(compare) => compare.ahead_by,
+                None => return Ok(Outcome::NoBranch),
+            };
+            if ahead == 0 {
+                return Ok(Outcome::NoChanges);
+            }
+
+            let open = github::get_open_pull_requests(repo, Some(&self.head), token)?;
+            if let Some(pr) = open.into_iter().next() {
+                return Ok(Outcome::Exists(pr));
+            }
+
+            let title = match &self.title_script {
+                Some(script) => script
+                    .execute_and_get_output(&repo.name, &repo.owner)?
+                    .trim()
+                    .to_string(),
+                None => self.title.clone().unwrap_or_default(),
+            };
+            let body = match &self.body_script {
+                Some(script) => Some(script.execute_and_get_output(&repo.name, &repo.owner)?),
+                None => self.body.clone(),
+            };
+
+            let pull_request = CreatePullRequestBody {
+                title,
+                head: self.head.clone(),
+                base,
+                body,
+                draft: self.draft,
+            };
+            let pr = github::create_pull_request(repo, &pull_request, token)?;
+
+            Ok(Outcome::Created(pr))
+        };
+
+        let result = create();
+
+        // The pull request exists at this point, so a failure here doesn't fail the repo
+        let warning = match &result {
+            Ok(Outcome::Created(pr)) | Ok(Outcome::Exists(pr)) => {
+                self.add_reviewers_and_labels(repo, pr.number, token).err()
+            }
+            _ => None,
+        };
+
+        Status {
+            repo: repo.name.clone(),
+            result,
+            warning,
+        }
+    }
+
+    fn add_reviewers_and_labels(
+        &self,
+        repo: &RemoteRepo,
+        number: usize,
+        token: &str,
+    ) -> Result<()> {
+        if !self.reviewer.is_empty() || !self.team_reviewer.is_empty() {
+            github::request_reviewers(repo, number, &self.reviewer, &self.team_reviewer, token)?;
+        }
+
+        if !self.label.is_empty() {
+            github::add_labels(repo, number, &self.label, token)?;
+        }
+
+        Ok(())
+    }
+}
+
+enum Outcome {
+    Created(PullRequest),
+    Exists(PullRequest),
+    NoBranch,
+    NoChanges,
+}
+
+struct Status {
+    repo: String,
+    result: Result<Outcome, Error>,
+    /// Failure to add reviewers or labels to the pull request
+    warning: Option<Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        let url = match &self.result {
+            Ok(Outcome::Created(pr)) | Ok(Outcome::Exists(pr)) => pr.html_url.as_str(),
+            _ => "",
+        };
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            self.result_to_cell(),
+            cell!(l -> url),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(Outcome::Created(_)) => cell!(Fgl -> "Created"),
+            Ok(Outcome::Exists(_)) => cell!(Fyl -> "Already open"),
+            Ok(Outcome::NoBranch) => cell!(l -> "No branch"),
+            Ok(Outcome::NoChanges) => cell!(l -> "No changes"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn is_created(&self) -> bool {
+        matches!(self.result, Ok(Outcome::Created(_)))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+
+    fn to_warning_row(&self) -> Option<Row> {
+        let msg = format!("{:?}", self.warning.as_ref()?);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        Some(row!(cell!(b -> &self.repo), cell!(Fy -> lines.as_str())))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Pull request", "Url"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let created: Vec<_> = statuses.iter().filter(|s| s.is_created()).collect();
+
+    if !created.is_empty() {
+        let msg = format!("\nOpened {} pull requests successfully!", created.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+
+    let warnings: Vec<_> = statuses.iter().filter_map(|s| s.to_warning_row()).collect();
+    if !warnings.is_empty() {
+        let msg = format!(
+            "Could not add reviewers or labels to {} pull requests:",
+            warnings.len()
+        );
+        println!("\n{}\n", msg.yellow());
+
+        let mut warning_table = Table::init(warnings);
+        warning_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        warning_table.set_titles(row!["Repo", "Error"]);
+        warning_table.printstd();
+    }
+}
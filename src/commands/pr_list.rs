@@ -0,0 +1,137 @@
+use super::common;
+use crate::filter::{Filter, Filterable};
+use crate::github;
+use crate::github::{CheckState, MergeState, PullRequestStatus, ReviewDecision};
+use anyhow::Result;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// List open pull requests of all repositories that match a pattern
+///
+/// Every pull request is shown with its mergeable state, review decision and the combined state
+/// of the checks of its head commit.
+pub struct PrListArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long)]
+    /// Only show pull requests of this head branch
+    pub head: Option<String>,
+    #[structopt(long, short)]
+    /// Only show pull requests opened by this user
+    pub author: Option<String>,
+}
+
+impl PrListArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let prs = open_pull_requests(
+            &organisation,
+            self.regex.as_ref(),
+            self.head.as_deref(),
+            self.author.as_deref(),
+            &user_token,
+        )?;
+
+        if prs.is_empty() {
+            println!(
+                "There is no open pull requests in organisation {} that matches",
+                organisation
+            );
+            return Ok(());
+        }
+
+        let rows: Vec<_> = prs.iter().map(to_row).collect();
+        let mut table = Table::init(rows);
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row![
+            "Repo",
+            "#",
+            "Title",
+            "Branch",
+            "Author",
+            "Mergeable",
+            "Review",
+            "Checks"
+        ]);
+        table.printstd();
+
+        let ready = prs.iter().filter(|pr| pr.is_ready()).count();
+        println!(
+            "\n{} open pull requests, {} are ready to merge",
+            prs.len(),
+            ready
+        );
+
+        Ok(())
+    }
+}
+
+/// Open pull requests of an organisation filtered by repository, head branch and author
+pub fn open_pull_requests(
+    organisation: &str,
+    regex: Option<&Filter>,
+    head: Option<&str>,
+    author: Option<&str>,
+    token: &str,
+) -> Result<Vec<PullRequestStatus>> {
+    let prs = github::list_open_pull_requests(token, organisation, head)?;
+    let mut prs: Vec<_> = PullRequestStatus::filter_with_option(prs, regex)
+        .into_iter()
+        .filter(|pr| author.is_none() || pr.author.as_deref() == author)
+        .collect();
+    prs.sort_by(|a, b| a.repo.cmp(&b.repo).then(a.number.cmp(&b.number)));
+    Ok(prs)
+}
+
+fn to_row(pr: &PullRequestStatus) -> Row {
+    let title = if pr.draft {
+        format!("[draft] {}", pr.title)
+    } else {
+        pr.title.clone()
+    };
+    Row::new(vec![
+        cell!(b -> &pr.repo),
+        cell!(r -> pr.number),
+        cell!(title),
+        cell!(format!("{} -> {}", pr.head, pr.base)),
+        cell!(pr.author.as_deref().unwrap_or("-")),
+        mergeable_cell(&pr.mergeable),
+        review_cell(pr.review.as_ref()),
+        checks_cell(pr.checks.as_ref()),
+    ])
+}
+
+fn mergeable_cell(mergeable: &MergeState) -> Cell {
+    match mergeable {
+        MergeState::Mergeable => cell!(Fg -> "Yes"),
+        MergeState::Conflicting => cell!(Fr -> "Conflict"),
+        MergeState::Unknown => cell!(Fy -> "Unknown"),
+    }
+}
+
+fn review_cell(review: Option<&ReviewDecision>) -> Cell {
+    match review {
+        Some(ReviewDecision::Approved) => cell!(Fg -> "Approved"),
+        Some(ReviewDecision::ChangesRequested) => cell!(Fr -> "Changes requested"),
+        Some(ReviewDecision::ReviewRequired) => cell!(Fy -> "Required"),
+        None => cell!("Not required"),
+    }
+}
+
+fn checks_cell(checks: Option<&CheckState>) -> Cell {
+    match checks {
+        Some(CheckState::Success) => cell!(Fg -> "Passed"),
+        Some(CheckState::Pending) => cell!(Fy -> "Pending"),
+        Some(CheckState::Failure) => cell!(Fr -> "Failed"),
+        None => cell!("-"),
+    }
+}
@@ -0,0 +1,257 @@
+use super::common;
+use super::pr_list::open_pull_requests;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::{CheckState, MergeState, PullRequestStatus};
+use anyhow::{anyhow, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Merge open pull requests of a head branch in all repositories that match a pattern
+///
+/// Only pull requests that are approved, have all checks passed and have no conflicts are merged,
+/// the others are skipped. Pull requests in repositories that don't require reviews count as
+/// approved. Draft pull requests are always skipped.
+pub struct PrMergeArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long)]
+    /// Head branch of the pull requests
+    pub head: String,
+    #[structopt(long, short)]
+    /// Only merge pull requests opened by this user
+    pub author: Option<String>,
+    #[structopt(long, short, default_value = "merge")]
+    /// Merge method, either merge, squash or rebase
+    pub method: MergeMethod,
+    #[structopt(long, short)]
+    /// Option to delete the head branch after merging
+    pub delete_branch: bool,
+}
+
+#[derive(Debug)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl fmt::Display for MergeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for MergeMethod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "merge" => Ok(MergeMethod::Merge),
+            "squash" => Ok(MergeMethod::Squash),
+            "rebase" => Ok(MergeMethod::Rebase),
+            _ => Err(anyhow!("Method has to be merge, squash or rebase")),
+        }
+    }
+}
+
+impl PrMergeArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let prs = open_pull_requests(
+            &organisation,
+            self.regex.as_ref(),
+            Some(&self.head),
+            self.author.as_deref(),
+            &user_token,
+        )?;
+
+        if prs.is_empty() {
+            println!(
+                "There is no open pull requests for branch {} in organisation {}",
+                self.head, organisation
+            );
+            return Ok(());
+        }
+
+        let ready = prs.iter().filter(|pr| pr.is_ready()).count();
+        if ready > 0 && !confirm(ready, &self.method)? {
+            println!("Command is aborted. Nothing is merged!");
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = prs
+            .par_iter()
+            .map(|pr| self.merge(pr, &organisation, &user_token))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+
+    fn merge(&self, pr: &PullRequestStatus, owner: &str, token: &str) -> Status {
+        log::info!("Processing pull request {}#{}", pr.repo, pr.number);
+
+        let merge = || -> Result<Outcome> {
+            if let Some(reason) = skip_reason(pr) {
+                return Ok(Outcome::Skipped(reason));
+            }
+
+            let method = self.method.to_string();
+            github::merge_pull_request(&pr.repo, owner, pr.number, &method, token)?;
+
+            if self.delete_branch {
+                github::delete_branch(&pr.repo, owner, &pr.head, token)?;
+                return Ok(Outcome::Merged { deleted: true });
+            }
+
+            Ok(Outcome::Merged { deleted: false })
+        };
+
+        Status {
+            repo: pr.repo.clone(),
+            number: pr.number,
+            result: merge(),
+        }
+    }
+}
+
+fn skip_reason(pr: &PullRequestStatus) -> Option<&'static str> {
+    if pr.draft {
+        return Some("Draft");
+    }
+    match pr.mergeable {
+        MergeState::Conflicting => return Some("Conflict"),
+        MergeState::Unknown => return Some("Mergeable state unknown"),
+        MergeState::Mergeable => {}
+    }
+    if !pr.is_approved() {
+        return Some("Not approved");
+    }
+    match pr.checks {
+        Some(CheckState::Success) => None,
+        Some(CheckState::Pending) => Some("Checks pending"),
+        Some(CheckState::Failure) => Some("Checks failed"),
+        None => Some("No checks"),
+    }
+}
+
+fn confirm(count: usize, method: &MergeMethod) -> Result<bool> {
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to {} {} pull request(s)?\nEnter {} to continue",
+            method, count, key
+        ),
+        key,
+    )
+}
+
+enum Outcome {
+    Merged { deleted: bool },
+    Skipped(&'static str),
+}
+
+struct Status {
+    repo: String,
+    number: i64,
+    result: Result<Outcome, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            cell!(r -> self.number),
+            self.result_to_cell(),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(Outcome::Merged { deleted: true }) => cell!(Fgl -> "Merged, branch deleted"),
+            Ok(Outcome::Merged { deleted: false }) => cell!(Fgl -> "Merged"),
+            Ok(Outcome::Skipped(reason)) => cell!(Fyl -> format!("Skipped: {}", reason)),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn is_merged(&self) -> bool {
+        matches!(self.result, Ok(Outcome::Merged { .. }))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "#", "Result"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let merged: Vec<_> = statuses.iter().filter(|s| s.is_merged()).collect();
+
+    if !merged.is_empty() {
+        let msg = format!("\nMerged {} pull requests successfully!", merged.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -1,4 +1,5 @@
 use super::common;
+use super::resume::{resume, Resume};
 use crate::filter::Filter;
 use crate::git;
 use crate::git::GitCredential;
@@ -28,10 +29,19 @@ pub struct PullArgs {
     pub regex: Option<Filter>,
     #[structopt(long, short)]
     /// Option to stash if there are unstaged changes
+    ///
+    /// The stashed changes will be brought back after pulling. If they conflict with the pulled
+    /// changes, they are kept in the stash.
     pub stash: bool,
     #[structopt(long, short)]
     /// Option to create a merge commit instead of rebase
     pub merge: bool,
+    #[structopt(long = "continue", conflicts_with = "abort")]
+    /// Continue unfinished merges and rebases after the conflicts are resolved and added
+    pub resume: bool,
+    #[structopt(long)]
+    /// Abort unfinished merges and rebases
+    pub abort: bool,
 }
 
 impl PullArgs {
@@ -40,6 +50,13 @@ impl PullArgs {
         let root = common::root()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
 
+        if self.resume {
+            return resume(&organisation, self.regex.as_ref(), Resume::Continue, true);
+        }
+        if self.abort {
+            return resume(&organisation, self.regex.as_ref(), Resume::Abort, true);
+        }
+
         let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
 
         if sub_dirs.is_empty() {
@@ -73,7 +90,7 @@ fn summarize(statuses: &[Status]) {
         .collect();
     let stashes: Vec<_> = statuses
         .iter()
-        .filter(|s| s.stash_status.is_success())
+        .filter(|s| s.stash_status.is_kept())
         .collect();
 
     if !success_create.is_empty() {
@@ -90,7 +107,7 @@ fn summarize(statuses: &[Status]) {
     }
 
     if !stashes.is_empty() {
-        let msg = format!("There are {} repos have been stashed that need to use \"stash apply\" to bring the changes back", stashes.len());
+        let msg = format!("There are {} repos whose stashed changes cannot be brought back after pulling. They are kept in the stash, use \"gut stash pop\" after resolving", stashes.len());
         println!("{}\n", msg.yellow());
     }
 
@@ -144,15 +161,22 @@ fn pull(dir: &PathBuf, user: &User, stash: bool, merge: bool) -> Status {
                 repo_status = RepoStatus::Dirty;
 
                 if stash {
-                    // do stash
-                    stash_status = match git::stash(&mut git_repo, None) {
-                        Ok(_) => StashStatus::Success,
-                        Err(e) => StashStatus::Failed(e),
-                    };
+                    if let Err(e) = git::stash(&mut git_repo, Some("gut pull")) {
+                        stash_status = StashStatus::Failed(e);
+                        return Ok(PullStatus::Nothing);
+                    }
                     // pull
                     let cred = GitCredential::from(user);
-                    let status = git::pull(&git_repo, "origin", Some(cred), merge)?;
-                    return Ok(status);
+                    let result = git::pull(&git_repo, "origin", Some(cred), merge);
+                    // bring the changes back unless pull has left conflicts in the worktree
+                    stash_status = match result {
+                        Ok(PullStatus::WithConflict) => StashStatus::Kept,
+                        _ => match git::stash_pop(&mut git_repo, 0) {
+                            Ok(_) => StashStatus::Restored,
+                            Err(_) => StashStatus::Kept,
+                        },
+                    };
+                    return result;
                 }
             } else {
                 repo_status = RepoStatus::Conflict;
@@ -200,7 +224,10 @@ impl Status {
 
     fn is_success(&self) -> bool {
         self.status.is_ok()
-            && (self.stash_status.is_success() || matches!(self.stash_status, StashStatus::No))
+            && matches!(
+                self.stash_status,
+                StashStatus::No | StashStatus::Restored | StashStatus::Kept
+            )
     }
 
     fn has_error(&self) -> bool {
@@ -237,7 +264,8 @@ fn merge_status_to_cell(status: &PullStatus) -> Cell {
 enum StashStatus {
     No,
     Skip,
-    Success,
+    Restored,
+    Kept,
     Failed(Error),
 }
 
@@ -246,13 +274,14 @@ impl StashStatus {
         match &self {
             StashStatus::No => cell!(r -> "-"),
             StashStatus::Skip => cell!(r -> "-"),
-            StashStatus::Success => cell!(Fgr -> "Success"),
+            StashStatus::Restored => cell!(Fgr -> "Restored"),
+            StashStatus::Kept => cell!(Fyr -> "Kept in stash"),
             StashStatus::Failed(_) => cell!(Frr -> "Failed"),
         }
     }
 
-    fn is_success(&self) -> bool {
-        matches!(self, StashStatus::Success)
+    fn is_kept(&self) -> bool {
+        matches!(self, StashStatus::Kept)
     }
 }
 
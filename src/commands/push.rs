@@ -4,11 +4,11 @@ use colored::*;
 use prettytable::{cell, format, row, Cell, Row, Table};
 
 use crate::git;
-use anyhow::{Context, Error, Result};
+use anyhow::{anyhow, Context, Error, Result};
 
 use crate::filter::Filter;
-use crate::git::push;
-use crate::git::GitCredential;
+use crate::git::{GitCredential, RefUpdate};
+use git2::BranchType;
 use structopt::StructOpt;
 
 use crate::commands::topic_helper;
@@ -20,6 +20,8 @@ use rayon::prelude::*;
 /// Push the provided branch to remote server for all repositories that match a pattern
 /// or a topic
 ///
+/// Upstream of the branch will be set on the first push.
+///
 /// This command will do nothing if there is nothing to push
 pub struct PushArgs {
     #[structopt(long, short)]
@@ -35,6 +37,12 @@ pub struct PushArgs {
     pub topic: Option<String>,
     #[structopt(long, short, default_value = "main")]
     pub branch: String,
+    #[structopt(long)]
+    /// Overwrite the remote branch, but only if it is still at the commit we last fetched
+    pub force_with_lease: bool,
+    #[structopt(long)]
+    /// Option to push all local tags as well
+    pub tags: bool,
     #[structopt(long, short)]
     pub use_https: bool,
 }
@@ -62,13 +70,104 @@ impl PushArgs {
 
         let statuses: Vec<_> = filtered_repos
             .par_iter()
-            .map(|r| push_branch(&r, &self.branch, &user, &"origin", self.use_https))
+            .map(|r| self.push_branch(&r, &user, &"origin"))
             .collect();
 
         summarize(&statuses, &self.branch);
 
         Ok(())
     }
+
+    fn push_branch(&self, repo: &RemoteRepo, user: &User, remote_name: &str) -> Status {
+        log::info!("Processing repo {}", repo.name);
+
+        let branch = &self.branch;
+        let mut updates = vec![];
+
+        let mut push = || -> Result<PushStatus> {
+            let git_repo = try_from_one(repo.clone(), user, self.use_https)?;
+            let git_repo = git_repo
+                .open()
+                .with_context(|| format!("{:?} is not a git directory.", git_repo.local_path))?;
+
+            let local_oid = git_repo
+                .find_branch(branch, BranchType::Local)
+                .map_err(|_| anyhow!("There is no local branch with name: {}", branch))?
+                .get()
+                .target()
+                .ok_or_else(|| anyhow!("Branch {} is not a direct reference", branch))?;
+
+            let tracking_oid = git_repo
+                .find_branch(&format!("{}/{}", remote_name, branch), BranchType::Remote)
+                .ok()
+                .and_then(|b| b.get().target());
+
+            let should_push_branch = match tracking_oid {
+                Some(oid) => git_repo.graph_ahead_behind(local_oid, oid)?.0 > 0,
+                None => true,
+            };
+
+            let mut refspecs = vec![];
+            if should_push_branch {
+                refspecs.push(common_ref(branch, self.force_with_lease));
+            }
+            if self.tags {
+                for tag in git_repo.tag_names(None)?.iter().flatten() {
+                    refspecs.push(format!("refs/tags/{}:refs/tags/{}", tag, tag));
+                }
+            }
+
+            if refspecs.is_empty() {
+                return Ok(PushStatus::No);
+            }
+
+            let cred = GitCredential::from(user);
+            let mut remote = git_repo.find_remote(remote_name)?;
+
+            if self.force_with_lease && should_push_branch {
+                let remote_refs = git::remote_refs(&mut remote, Some(cred.clone()))?;
+                let actual = remote_refs.get(&format!("refs/heads/{}", branch)).copied();
+                if actual != tracking_oid {
+                    return Err(anyhow!(
+                        "Remote branch {} has changed since the last fetch. Fetch it first.",
+                        branch
+                    ));
+                }
+            }
+
+            updates = git::push_refs(&mut remote, &refspecs, Some(cred))?;
+
+            if updates.iter().any(|u| u.is_rejected()) {
+                return Ok(PushStatus::Rejected);
+            }
+
+            let upstream_set =
+                should_push_branch && git::track_remote_branch(&git_repo, branch, remote_name)?;
+
+            Ok(PushStatus::Success { upstream_set })
+        };
+
+        let status = match push() {
+            Ok(status) => status,
+            Err(e) => PushStatus::Failed(e),
+        };
+
+        Status {
+            repo: repo.clone(),
+            status,
+            updates,
+        }
+    }
+}
+
+/// Refspec to push a branch, forced if `force` is true
+fn common_ref(branch: &str, force: bool) -> String {
+    let refspec = format!("refs/heads/{}:refs/heads/{}", branch, branch);
+    if force {
+        format!("+{}", refspec)
+    } else {
+        refspec
+    }
 }
 
 fn summarize(statuses: &[Status], branch: &str) {
@@ -103,73 +202,57 @@ fn summarize(statuses: &[Status], branch: &str) {
     }
 }
 
-fn push_branch(
-    repo: &RemoteRepo,
-    branch: &str,
-    user: &User,
-    remote_name: &str,
-    use_https: bool,
-) -> Status {
-    log::info!("Processing repo {}", repo.name);
-
-    let mut push_status = PushStatus::No;
-
-    let mut push = || -> Result<()> {
-        let git_repo = try_from_one(repo.clone(), user, use_https)?;
-        let git_repo = git_repo
-            .open()
-            .with_context(|| format!("{:?} is not a git directory.", git_repo.local_path))?;
-
-        let status = git::status(&git_repo, false)?;
-
-        if !status.should_push() {
-            push_status = PushStatus::No;
-            return Ok(());
-        }
-
-        let cred = GitCredential::from(user);
-        push::push_branch(&git_repo, branch, remote_name, Some(cred))?;
-        push_status = PushStatus::Success(status.is_ahead);
-        Ok(())
-    };
-
-    let result = push();
-    if let Err(e) = result {
-        push_status = PushStatus::Failed(e);
-    }
-
-    Status {
-        repo: repo.clone(),
-        status: push_status,
-    }
-}
-
 struct Status {
     repo: RemoteRepo,
     status: PushStatus,
+    updates: Vec<RefUpdate>,
 }
 
 impl Status {
     fn to_row(&self) -> Row {
-        Row::new(vec![cell!(b -> &self.repo.name), self.status.to_cell()])
+        Row::new(vec![
+            cell!(b -> &self.repo.name),
+            self.status.to_cell(),
+            self.updates_to_cell(),
+        ])
+    }
+
+    fn updates_to_cell(&self) -> Cell {
+        let lines: Vec<_> = self
+            .updates
+            .iter()
+            .map(|u| match &u.rejection {
+                Some(_) => format!("{} rejected", u.shorthand()),
+                None => format!("{} ok", u.shorthand()),
+            })
+            .collect();
+        cell!(l -> lines.join("\n"))
     }
 
     fn has_error(&self) -> bool {
-        matches!(self.status, PushStatus::Failed(_))
+        matches!(self.status, PushStatus::Failed(_) | PushStatus::Rejected)
     }
 
     fn success(&self) -> bool {
-        matches!(self.status, PushStatus::Success(_))
+        matches!(self.status, PushStatus::Success { .. })
     }
 
     fn to_error_row(&self) -> Row {
-        let e = if let PushStatus::Failed(e) = &self.status {
-            e
-        } else {
-            panic!("This should have an error here");
+        let msg = match &self.status {
+            PushStatus::Failed(e) => format!("{:?}", e),
+            PushStatus::Rejected => self
+                .updates
+                .iter()
+                .filter_map(|u| {
+                    u.rejection
+                        .as_ref()
+                        .map(|msg| format!("{} is rejected: {}", u.refname, msg))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => panic!("This should have an error here"),
         };
 
-        let msg = format!("{:?}", e);
         let lines = common::sub_strings(msg.as_str(), 80);
         let lines = lines.join("\n");
         row!(cell!(b -> &self.repo.name), cell!(Fr -> lines.as_str()))
@@ -178,7 +261,8 @@ impl Status {
 
 enum PushStatus {
     No,
-    Success(usize),
+    Success { upstream_set: bool },
+    Rejected,
     Failed(Error),
 }
 
@@ -186,7 +270,9 @@ impl PushStatus {
     fn to_cell(&self) -> Cell {
         match &self {
             PushStatus::No => cell!(r -> "-"),
-            PushStatus::Success(_) => cell!(Fgr -> "Success"),
+            PushStatus::Success { upstream_set: true } => cell!(Fgr -> "Success, upstream set"),
+            PushStatus::Success { .. } => cell!(Fgr -> "Success"),
+            PushStatus::Rejected => cell!(Frr -> "Rejected"),
             PushStatus::Failed(_) => cell!(Frr -> "Failed"),
         }
     }
@@ -196,6 +282,6 @@ fn to_table(statuses: &[Status]) -> Table {
     let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
     let mut table = Table::init(rows);
     table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
-    table.set_titles(row!["Repo", "Status"]);
+    table.set_titles(row!["Repo", "Status", "Refs"]);
     table
 }
@@ -0,0 +1,18 @@
+use super::release_create::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create GitHub releases for all repositories that match a pattern
+pub enum ReleaseArgs {
+    #[structopt(name = "create")]
+    Create(ReleaseCreateArgs),
+}
+
+impl ReleaseArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            ReleaseArgs::Create(args) => args.run(),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use super::common;
+use crate::filter::{Filter, Glob};
+use crate::git;
+use crate::github;
+use crate::github::{CreateReleaseBody, RemoteRepo};
+use crate::path;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use git2::Repository;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::fs;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create a GitHub release from an existing tag for all repositories that match a pattern
+///
+/// The tag must exist in the local repository. The release notes are built from the commits
+/// between the previous tag and this tag, unless --generate-notes is used.
+pub struct ReleaseCreateArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Tag name of the release
+    pub tag: String,
+    #[structopt(long, short)]
+    /// Release title, default is the tag name
+    pub name: Option<String>,
+    #[structopt(long, short)]
+    /// Option to let GitHub generate the release notes
+    pub generate_notes: bool,
+    #[structopt(long)]
+    /// Option to create a draft release
+    pub draft: bool,
+    #[structopt(long)]
+    /// Option to mark the release as a prerelease
+    pub prerelease: bool,
+    #[structopt(long, short)]
+    /// Optional path pattern of assets to upload, e.g. dist/*.zip
+    ///
+    /// It is matched against the paths of all files relative to the root of each local
+    /// repository. A pattern without / matches files with that name in any directory.
+    pub assets: Option<Glob>,
+}
+
+impl ReleaseCreateArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| self.release(r, &root, &user_token))
+            .collect();
+
+        summarize(&statuses, &self.tag);
+
+        Ok(())
+    }
+
+    fn release(&self, repo: &RemoteRepo, root: &str, token: &str) -> Status {
+        log::info!("Processing repo {}", repo.name);
+
+        let release = || -> Result<Release> {
+            let local_path = path::local_path_repo(&repo.owner, &repo.name, root);
+            if !local_path.exists() {
+                return Err(anyhow!(
+                    "There is no local repository at {:?}. Please clone it first.",
+                    local_path
+                ));
+            }
+
+            let git_repo = git::open(&local_path)
+                .with_context(|| format!("{:?} is not a git directory.", local_path))?;
+
+            let commit = git::tag_commit(&git_repo, &self.tag)
+                .with_context(|| format!("There is no tag {} in this repository", self.tag))?;
+
+            let body = if self.generate_notes {
+                None
+            } else {
+                Some(release_notes(&git_repo, &self.tag)?)
+            };
+
+            let release = CreateReleaseBody {
+                tag_name: self.tag.clone(),
+                target_commitish: commit.to_string(),
+                name: self.name.clone().unwrap_or_else(|| self.tag.clone()),
+                body,
+                draft: self.draft,
+                prerelease: self.prerelease,
+                generate_release_notes: self.generate_notes,
+            };
+            let response = github::create_release(repo, &release, token)?;
+
+            // The release exists from here on, so failed uploads are reported with it
+            let mut assets = 0;
+            let mut asset_errors = vec![];
+            if let Some(glob) = &self.assets {
+                for file in path::all_files(&local_path) {
+                    if !glob.is_match(&file) {
+                        continue;
+                    }
+                    let upload = || -> Result<()> {
+                        let file_path = local_path.join(&file);
+                        let name = path::dir_name(&file_path)?;
+                        let content = fs::read(&file_path)
+                            .with_context(|| format!("Cannot read asset {:?}", file_path))?;
+                        github::upload_release_asset(&response, &name, content, token)
+                            .with_context(|| format!("Cannot upload asset {}", file))
+                    };
+                    match upload() {
+                        Ok(_) => assets += 1,
+                        Err(e) => asset_errors.push(e),
+                    }
+                }
+            }
+
+            Ok(Release {
+                url: response.html_url,
+                assets,
+                asset_errors,
+            })
+        };
+
+        let result = release();
+
+        Status {
+            repo: repo.name.clone(),
+            result,
+        }
+    }
+}
+
+/// Release notes listing the commits between the previous tag and `tag`
+fn release_notes(repo: &Repository, tag: &str) -> Result<String> {
+    let to = git::tag_commit(repo, tag)?;
+    let previous = git::previous_tag(repo, tag)?;
+    let from = match &previous {
+        Some(previous) => Some(git::tag_commit(repo, previous)?),
+        None => None,
+    };
+
+    let commits = git::commits_between(repo, from, to)?;
+
+    let title = match &previous {
+        Some(previous) => format!("## Changes since {}", previous),
+        None => "## Changes".to_string(),
+    };
+    let lines: Vec<_> = commits
+        .iter()
+        .map(|(oid, summary)| {
+            let sha = oid.to_string();
+            format!("- {} ({})", summary, &sha[..8])
+        })
+        .collect();
+
+    Ok(format!("{}\n\n{}\n", title, lines.join("\n")))
+}
+
+struct Release {
+    url: String,
+    assets: usize,
+    asset_errors: Vec<Error>,
+}
+
+struct Status {
+    repo: String,
+    result: Result<Release, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        let assets = match &self.result {
+            Ok(release) if !release.asset_errors.is_empty() => {
+                format!("{} ({} failed)", release.assets, release.asset_errors.len())
+            }
+            Ok(release) if release.assets > 0 => release.assets.to_string(),
+            _ => "-".to_string(),
+        };
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            self.result_to_cell(),
+            cell!(r -> assets),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(release) => cell!(Fgl -> release.url),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+
+    fn to_asset_error_rows(&self) -> Vec<Row> {
+        let errors = match &self.result {
+            Ok(release) => &release.asset_errors,
+            Err(_) => return vec![],
+        };
+
+        errors
+            .iter()
+            .map(|e| {
+                let msg = format!("{:?}", e);
+                let lines = common::sub_strings(msg.as_str(), 80);
+                let lines = lines.join("\n");
+                row!(cell!(b -> &self.repo), cell!(Fy -> lines.as_str()))
+            })
+            .collect()
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Release", "Assets"]);
+    table
+}
+
+fn summarize(statuses: &[Status], tag: &str) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!(
+            "\nCreated release {} in {} repos successfully!",
+            tag,
+            successes.len()
+        );
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+
+    let asset_errors: Vec<_> = statuses
+        .iter()
+        .flat_map(|s| s.to_asset_error_rows())
+        .collect();
+    if !asset_errors.is_empty() {
+        let msg = format!(
+            "The releases were created, but {} assets could not be uploaded:",
+            asset_errors.len()
+        );
+        println!("\n{}\n", msg.yellow());
+
+        let mut asset_table = Table::init(asset_errors);
+        asset_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        asset_table.set_titles(row!["Repo", "Error"]);
+        asset_table.printstd();
+    }
+}
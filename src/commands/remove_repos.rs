@@ -7,6 +7,9 @@ use anyhow::Result;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
+/// Delete all repositories that match a pattern
+///
+/// With --team-slug the repositories are kept, and only the access of that team to them is removed
 pub struct RemoveReposArgs {
     #[structopt(long, short)]
     /// Target organisation name
@@ -16,6 +19,9 @@ pub struct RemoveReposArgs {
     #[structopt(long, short)]
     /// Optional regex to filter repositories
     pub regex: Option<Filter>,
+    #[structopt(long)]
+    /// Slug of a team whose access to the repositories is removed
+    pub team_slug: Option<String>,
 }
 
 impl RemoveReposArgs {
@@ -34,6 +40,15 @@ impl RemoveReposArgs {
             return Ok(());
         }
 
+        if let Some(team) = &self.team_slug {
+            if confirm_team(&filtered_repos, team)? {
+                remove_team(&filtered_repos, team, &user_token);
+            } else {
+                println!("Command is aborted. Nothing is changed!")
+            }
+            return Ok(());
+        }
+
         let is_confirmed = confirm(&filtered_repos)?;
         if is_confirmed {
             remove(&filtered_repos, &user_token)?;
@@ -71,3 +86,40 @@ fn remove(repos: &[RemoteRepo], token: &str) -> Result<()> {
     }
     Ok(())
 }
+
+fn confirm_team(repos: &[RemoteRepo], team: &str) -> Result<bool> {
+    println!("Team {} will lose access to the following repos:", team);
+
+    for repo in repos {
+        println!("{}", repo.full_name());
+    }
+
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to remove access of team {} to {} repo(s)?\nEnter {} to continue",
+            team,
+            repos.len(),
+            key
+        ),
+        key,
+    )
+}
+
+fn remove_team(repos: &[RemoteRepo], team: &str, token: &str) {
+    for repo in repos {
+        match github::remove_repo_from_team(repo, team, token) {
+            Ok(_) => println!(
+                "Removed access of team {} to repo {} successfully",
+                team,
+                repo.full_name()
+            ),
+            Err(e) => println!(
+                "Failed to remove access of team {} to repo {} because {:?}",
+                team,
+                repo.full_name(),
+                e
+            ),
+        }
+    }
+}
@@ -34,6 +34,7 @@ impl RemoveUsersArgs {
     fn remove_users_from_org(&self) -> Result<()> {
         let user_token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
 
         let users: Vec<String> = self.users.iter().map(|s| s.to_string()).collect();
 
@@ -47,6 +48,7 @@ impl RemoveUsersArgs {
     fn remove_users_from_team(&self, team_name: &str) -> Result<()> {
         let user_token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
 
         let users: Vec<String> = self.users.iter().map(|s| s.to_string()).collect();
 
@@ -0,0 +1,416 @@
+use super::common;
+use super::patterns;
+use crate::filter::{Filter, Glob};
+use crate::git;
+use crate::path;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use git2::Repository;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Search and replace text in all local repositories that match a pattern
+///
+/// The working tree is searched, files ignored by .gitignore and binary files are skipped. A diff
+/// of every change is shown first, and the files are only written after a confirmation.
+///
+/// The replacement works the same as in templates: the pattern is case insensitive and the
+/// replacement text can use capture groups like $1.
+pub struct ReplaceArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Regex to search for
+    pub pattern: String,
+    #[structopt(long, short)]
+    /// Replacement text
+    pub with: String,
+    #[structopt(long, short, number_of_values = 1)]
+    /// Optional path globs to limit the replacement, ex: "**/*.am"
+    ///
+    /// A glob without / is matched against file names only. Repeat the option for more globs
+    pub glob: Vec<Glob>,
+    #[structopt(long, short)]
+    /// Optional commit message, the changed files will be committed with it
+    ///
+    /// Repositories with uncommitted changes will be skipped
+    pub message: Option<String>,
+    #[structopt(long, short, requires = "message")]
+    /// Optional new branch to commit the changes on
+    pub branch: Option<String>,
+}
+
+impl ReplaceArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let mut replacements = BTreeMap::new();
+        replacements.insert(self.pattern.clone(), self.with.clone());
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let previews: Vec<_> = sub_dirs
+            .par_iter()
+            .map(|d| self.preview(d, &replacements))
+            .collect();
+
+        for preview in &previews {
+            preview.print();
+        }
+
+        let changes: Vec<_> = previews
+            .iter()
+            .filter(|p| matches!(&p.result, Ok(files) if !files.is_empty()))
+            .collect();
+        let errors: Vec<_> = previews.iter().filter(|p| p.result.is_err()).collect();
+
+        if !errors.is_empty() {
+            let msg = format!("There are {} repos that will be skipped:", errors.len());
+            println!("\n{}\n", msg.red());
+
+            let mut error_table = Table::new();
+            error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+            error_table.set_titles(row!["Repo", "Error"]);
+            for error in errors {
+                error_table.add_row(error.to_error_row());
+            }
+            error_table.printstd();
+        }
+
+        if changes.is_empty() {
+            println!("\nThere is nothing to replace!");
+            return Ok(());
+        }
+
+        let file_count: usize = changes.iter().map(|p| p.file_count()).sum();
+        if !confirm(file_count, changes.len())? {
+            println!("Command is aborted. Nothing is changed!");
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = changes.par_iter().map(|p| self.write(p)).collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+
+    fn preview(&self, dir: &PathBuf, replacements: &BTreeMap<String, String>) -> Preview {
+        let mut dir_name = "".to_string();
+
+        let mut preview = || -> Result<Vec<FileChange>> {
+            dir_name = path::dir_name(dir)?;
+            log::info!("Processing repo {}", dir_name);
+
+            let git_repo =
+                git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+            if self.message.is_some() && !git::status(&git_repo, false)?.is_empty() {
+                return Err(anyhow!(
+                    "Repository has uncommitted changes. Please commit or stash them first."
+                ));
+            }
+
+            find_changes(&git_repo, &self.glob, replacements)
+        };
+
+        let result = preview();
+
+        Preview {
+            repo: dir_name,
+            dir: dir.clone(),
+            result,
+        }
+    }
+
+    fn write(&self, preview: &Preview) -> Status {
+        let write = || -> Result<Option<String>> {
+            let files = match &preview.result {
+                Ok(files) => files,
+                Err(_) => return Ok(None),
+            };
+
+            let git_repo = git::open(&preview.dir)
+                .with_context(|| format!("{:?} is not a git directory.", preview.dir))?;
+
+            if let Some(branch) = &self.branch {
+                let head = git_repo.head()?.peel_to_commit()?;
+                git_repo.branch(branch, &head, false)?;
+                git::checkout_local_branch(&git_repo, branch)?;
+            }
+
+            for file in files {
+                fs::write(preview.dir.join(&file.path), &file.new)?;
+            }
+
+            match &self.message {
+                Some(msg) => {
+                    commit(&git_repo, files, msg)?;
+                    Ok(Some(git::head_shorthand(&git_repo)?))
+                }
+                None => Ok(None),
+            }
+        };
+
+        Status {
+            repo: preview.repo.clone(),
+            files: preview.file_count(),
+            result: write(),
+        }
+    }
+}
+
+/// Changes of the working tree files that match one of the globs, or all files if there is none
+fn find_changes(
+    git_repo: &Repository,
+    globs: &[Glob],
+    replacements: &BTreeMap<String, String>,
+) -> Result<Vec<FileChange>> {
+    let mut changes = vec![];
+    let mut error = None;
+    git::for_each_worktree_file(git_repo, |path, content| {
+        if error.is_some() {
+            return;
+        }
+        if !globs.is_empty() && !globs.iter().any(|g| g.is_match(path)) {
+            return;
+        }
+        let old = match std::str::from_utf8(content) {
+            Ok(old) => old,
+            Err(_) => return,
+        };
+        match patterns::generate_string(replacements, old) {
+            Ok(new) if new != old => changes.push(FileChange {
+                path: path.to_string(),
+                old: old.to_string(),
+                new,
+            }),
+            Ok(_) => {}
+            Err(e) => error = Some(e),
+        }
+    })?;
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(changes)
+}
+
+fn commit(git_repo: &Repository, files: &[FileChange], msg: &str) -> Result<()> {
+    let mut index = git_repo.index()?;
+    for file in files {
+        index.add_path(Path::new(&file.path))?;
+    }
+    index.write()?;
+    git::commit_index(git_repo, &mut index, msg)?;
+    Ok(())
+}
+
+fn confirm(files: usize, repos: usize) -> Result<bool> {
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to write the changes to {} file(s) in {} repo(s)?\nEnter {} to continue",
+            files, repos, key
+        ),
+        key,
+    )
+}
+
+struct FileChange {
+    path: String,
+    old: String,
+    new: String,
+}
+
+struct Preview {
+    repo: String,
+    dir: PathBuf,
+    result: Result<Vec<FileChange>, Error>,
+}
+
+impl Preview {
+    fn print(&self) {
+        let files = match &self.result {
+            Ok(files) => files,
+            Err(_) => return,
+        };
+
+        for file in files {
+            let diff = match git::diff_contents(&file.path, &file.old, &file.new) {
+                Ok(diff) => diff,
+                Err(_) => continue,
+            };
+            println!("{}", format!("{}: {}", self.repo, file.path).bold());
+            for line in diff.lines() {
+                if line.starts_with("+++") || line.starts_with("---") {
+                    println!("{}", line.bold());
+                } else if line.starts_with('+') {
+                    println!("{}", line.green());
+                } else if line.starts_with('-') {
+                    println!("{}", line.red());
+                } else if line.starts_with("@@") {
+                    println!("{}", line.cyan());
+                } else {
+                    println!("{}", line);
+                }
+            }
+            println!();
+        }
+    }
+
+    fn file_count(&self) -> usize {
+        match &self.result {
+            Ok(files) => files.len(),
+            Err(_) => 0,
+        }
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+struct Status {
+    repo: String,
+    files: usize,
+    /// Branch of the commit if the changes were committed
+    result: Result<Option<String>, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            cell!(r -> self.files),
+            self.result_to_cell(),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(Some(branch)) => cell!(Fgl -> format!("Committed to {}", branch)),
+            Ok(None) => cell!(Fgl -> "Written"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Files", "Result"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!("\nReplaced text in {} repos successfully!", successes.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_changes_with_glob() -> Result<()> {
+        let dir = tempdir()?;
+        let git_repo = Repository::init(dir.path())?;
+        fs::create_dir_all(dir.path().join("src/morphology"))?;
+        fs::write(dir.path().join("Makefile.am"), "SUBDIRS = src\n")?;
+        fs::write(
+            dir.path().join("src/morphology/Makefile.am"),
+            "SUBDIRS = .\n",
+        )?;
+        fs::write(dir.path().join("configure.ac"), "SUBDIRS = src\n")?;
+
+        let mut replacements = BTreeMap::new();
+        replacements.insert("SUBDIRS".to_string(), "DIST_SUBDIRS".to_string());
+        let globs = vec![Glob::from_str("**/*.am")?];
+
+        let changes = find_changes(&git_repo, &globs, &replacements)?;
+        let paths: Vec<_> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["Makefile.am", "src/morphology/Makefile.am"]);
+        assert_eq!(changes[0].new, "DIST_SUBDIRS = src\n");
+
+        dir.close()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::git::{MergeStatus, RebaseStatus};
+use crate::path;
+use anyhow::{Context, Error, Result};
+use colored::*;
+use git2::RepositoryState;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+/// What to do with repositories that are in the middle of a merge or a rebase
+#[derive(Debug, Clone, Copy)]
+pub enum Resume {
+    Continue,
+    Abort,
+}
+
+/// Continue or abort unfinished merges (and rebases if `include_rebase`) in all local
+/// repositories that match a pattern
+pub fn resume(
+    organisation: &str,
+    regex: Option<&Filter>,
+    action: Resume,
+    include_rebase: bool,
+) -> Result<()> {
+    let root = common::root()?;
+    let sub_dirs = common::read_dirs_for_org(organisation, &root, regex)?;
+
+    let statuses: Vec<_> = sub_dirs
+        .par_iter()
+        .map(|d| resume_one(d, action, include_rebase))
+        .filter(|s| !matches!(s.state, State::Clean))
+        .collect();
+
+    if statuses.is_empty() {
+        let what = if include_rebase {
+            "merge or rebase"
+        } else {
+            "merge"
+        };
+        println!(
+            "There is no unfinished {} in organisation {} that matches pattern {:?}",
+            what, organisation, regex
+        );
+        return Ok(());
+    }
+
+    summarize(&statuses);
+
+    Ok(())
+}
+
+fn resume_one(dir: &PathBuf, action: Resume, include_rebase: bool) -> Status {
+    let mut dir_name = "".to_string();
+    let mut state = State::Clean;
+
+    let mut resume = || -> Result<Outcome> {
+        dir_name = path::dir_name(dir)?;
+
+        let git_repo =
+            git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+        state = match git_repo.state() {
+            RepositoryState::Merge => State::Merging,
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge
+                if include_rebase =>
+            {
+                State::Rebasing
+            }
+            _ => State::Clean,
+        };
+
+        let outcome = match (&state, action) {
+            (State::Clean, _) => Outcome::Nothing,
+            (State::Merging, Resume::Continue) => match git::continue_merge(&git_repo)? {
+                MergeStatus::MergeWithConflict => Outcome::Conflicted,
+                _ => Outcome::Continued,
+            },
+            (State::Rebasing, Resume::Continue) => match git::continue_rebase(&git_repo)? {
+                RebaseStatus::RebaseWithConflict => Outcome::Conflicted,
+                _ => Outcome::Continued,
+            },
+            (State::Merging, Resume::Abort) => {
+                git::abort_merge(&git_repo)?;
+                Outcome::Aborted
+            }
+            (State::Rebasing, Resume::Abort) => {
+                git::abort_rebase(&git_repo)?;
+                Outcome::Aborted
+            }
+        };
+
+        Ok(outcome)
+    };
+
+    let result = resume();
+
+    Status {
+        repo: dir_name,
+        state,
+        result,
+    }
+}
+
+struct Status {
+    repo: String,
+    state: State,
+    result: Result<Outcome, Error>,
+}
+
+enum State {
+    Clean,
+    Merging,
+    Rebasing,
+}
+
+enum Outcome {
+    Nothing,
+    Continued,
+    Aborted,
+    Conflicted,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            self.state.to_cell(),
+            self.result_to_cell(),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(Outcome::Nothing) => cell!(r -> "-"),
+            Ok(Outcome::Continued) => cell!(Fgr -> "Finished"),
+            Ok(Outcome::Aborted) => cell!(Fgr -> "Aborted"),
+            Ok(Outcome::Conflicted) => cell!(Fyr -> "Still conflicted"),
+            Err(_) => cell!(Frr -> "Failed"),
+        }
+    }
+
+    fn is_conflicted(&self) -> bool {
+        matches!(self.result, Ok(Outcome::Conflicted))
+    }
+
+    fn is_success(&self) -> bool {
+        matches!(self.result, Ok(Outcome::Continued) | Ok(Outcome::Aborted))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+impl State {
+    fn to_cell(&self) -> Cell {
+        match self {
+            State::Clean => cell!(l -> "Clean"),
+            State::Merging => cell!(l -> "Merging"),
+            State::Rebasing => cell!(l -> "Rebasing"),
+        }
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "State", "Result"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| s.is_success()).collect();
+    let conflicts: Vec<_> = statuses.iter().filter(|s| s.is_conflicted()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!("\nProcessed {} repos successfully!", successes.len());
+        println!("{}", msg.green());
+    }
+
+    if !conflicts.is_empty() {
+        let msg = format!(
+            "\nThere are {} repos still conflicted. Resolve and add the conflicted files, then continue again",
+            conflicts.len()
+        );
+        println!("{}", msg.yellow());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -1,12 +1,13 @@
 use super::set_default_organisation::*;
 use super::set_info::*;
 use super::set_secret::*;
+use super::set_settings::*;
 use super::set_team_permission::*;
 use anyhow::Result;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-/// Set information, secret for repositories or permission for a team
+/// Set information, secret, settings for repositories or permission for a team
 pub enum SetArgs {
     #[structopt(name = "info")]
     Info(InfoArgs),
@@ -16,6 +17,8 @@ pub enum SetArgs {
     Permission(SetTeamPermissionArgs),
     #[structopt(name = "secret")]
     Secret(SecretArgs),
+    #[structopt(name = "settings")]
+    Settings(SettingsArgs),
 }
 
 impl SetArgs {
@@ -25,6 +28,7 @@ impl SetArgs {
             SetArgs::Organisation(args) => args.run(),
             SetArgs::Permission(args) => args.set_permission(),
             SetArgs::Secret(args) => args.run(),
+            SetArgs::Settings(args) => args.run(),
         }
     }
 }
@@ -0,0 +1,201 @@
+use super::common;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::{RemoteRepo, RepoSettings};
+use crate::toml::read_file;
+use anyhow::{anyhow, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Set merge options and features for all repositories that match a pattern
+///
+/// Settings can be given as options or read from a toml profile. Options override the
+/// values of the profile. Settings that are not given are left untouched.
+pub struct SettingsArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Toml file with the settings to apply, using the option names with underscores as keys
+    pub profile: Option<PathBuf>,
+    #[structopt(long)]
+    /// Allow squash merging pull requests
+    pub allow_squash_merge: Option<bool>,
+    #[structopt(long)]
+    /// Allow merging pull requests with a merge commit
+    pub allow_merge_commit: Option<bool>,
+    #[structopt(long)]
+    /// Allow rebase merging pull requests
+    pub allow_rebase_merge: Option<bool>,
+    #[structopt(long)]
+    /// Allow auto-merge on pull requests
+    pub allow_auto_merge: Option<bool>,
+    #[structopt(long)]
+    /// Delete head branches automatically when pull requests are merged
+    pub delete_branch_on_merge: Option<bool>,
+    #[structopt(long)]
+    /// Enable issues
+    pub has_issues: Option<bool>,
+    #[structopt(long)]
+    /// Enable the wiki
+    pub has_wiki: Option<bool>,
+    #[structopt(long)]
+    /// Enable projects
+    pub has_projects: Option<bool>,
+    #[structopt(long)]
+    /// Enable discussions
+    pub has_discussions: Option<bool>,
+    #[structopt(long)]
+    /// Make the repository a template repository
+    pub is_template: Option<bool>,
+    #[structopt(long)]
+    /// Require contributors to sign off on web-based commits
+    pub web_commit_signoff_required: Option<bool>,
+}
+
+impl SettingsArgs {
+    pub fn run(&self) -> Result<()> {
+        let settings = self.settings()?;
+        if settings.is_empty() {
+            return Err(anyhow!(
+                "There is no setting to change, please provide a profile or at least one option"
+            ));
+        }
+
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| set_settings(r, &settings, &user_token))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+
+    fn settings(&self) -> Result<RepoSettings> {
+        let profile = match &self.profile {
+            Some(path) => read_file(path)?,
+            None => RepoSettings::default(),
+        };
+
+        let options = RepoSettings {
+            allow_squash_merge: self.allow_squash_merge,
+            allow_merge_commit: self.allow_merge_commit,
+            allow_rebase_merge: self.allow_rebase_merge,
+            allow_auto_merge: self.allow_auto_merge,
+            delete_branch_on_merge: self.delete_branch_on_merge,
+            has_issues: self.has_issues,
+            has_wiki: self.has_wiki,
+            has_projects: self.has_projects,
+            has_discussions: self.has_discussions,
+            is_template: self.is_template,
+            web_commit_signoff_required: self.web_commit_signoff_required,
+        };
+
+        Ok(profile.merge(&options))
+    }
+}
+
+fn set_settings(repo: &RemoteRepo, settings: &RepoSettings, token: &str) -> Status {
+    log::info!("Processing repo {}", repo.name);
+
+    Status {
+        repo: repo.name.clone(),
+        result: github::set_repo_settings(repo, settings, token),
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<(), Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.repo), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(_) => cell!(Fgl -> "Updated"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Settings"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+
+    if errors.is_empty() {
+        let msg = format!(
+            "\nUpdated settings of {} repos successfully!",
+            statuses.len()
+        );
+        println!("{}", msg.green());
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
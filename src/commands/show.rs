@@ -1,17 +1,26 @@
+use super::show_access::*;
 use super::show_config::*;
 use super::show_repos::*;
+use super::show_settings::*;
+use super::show_teams::*;
 use super::show_users::*;
 use anyhow::Result;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-/// Show config, list of repositories or users
+/// Show config, list of repositories, their settings, access, teams or users
 pub enum ShowArgs {
+    #[structopt(name = "access")]
+    Access(ShowAccessArgs),
     #[structopt(name = "config")]
     // Show current configuration
     Config,
     #[structopt(name = "repositories", aliases = &["repos"])]
     Repos(ShowReposArgs),
+    #[structopt(name = "settings")]
+    Settings(ShowSettingsArgs),
+    #[structopt(name = "teams")]
+    Teams(ShowTeamsArgs),
     #[structopt(name = "users")]
     Users(ShowUsersArgs),
 }
@@ -19,8 +28,11 @@ pub enum ShowArgs {
 impl ShowArgs {
     pub fn run(&self) -> Result<()> {
         match self {
+            ShowArgs::Access(args) => args.run(),
             ShowArgs::Config => show_config(),
             ShowArgs::Repos(args) => args.show(),
+            ShowArgs::Settings(args) => args.run(),
+            ShowArgs::Teams(args) => args.run(),
             ShowArgs::Users(args) => args.run(),
         }
     }
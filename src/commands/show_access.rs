@@ -0,0 +1,446 @@
+use super::common;
+use crate::filter::Filter;
+use crate::github;
+use anyhow::{Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::write;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show a matrix of the permission of every user and team on all repositories that match a pattern
+///
+/// The permission of a user is the highest one they get as a collaborator, directly or through
+/// a team. Users that are not members of the organisation are outside collaborators.
+///
+/// Users with admin permission on a repository that none of their teams has admin permission on
+/// are flagged with !, except organisation owners.
+pub struct ShowAccessArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, parse(from_os_str))]
+    /// Option to also write the matrix to a csv file
+    pub csv: Option<PathBuf>,
+}
+
+impl ShowAccessArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
+
+        let mut filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+        filtered_repos.sort();
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let members: Vec<_> = github::get_org_members(&organisation, &user_token)?
+            .into_iter()
+            .map(|m| m.login)
+            .collect();
+        let owners = github::get_org_owners(&organisation, &user_token)?;
+
+        let teams: Result<Vec<_>> = github::get_teams(&organisation, &user_token)?
+            .par_iter()
+            .map(|t| fetch_team(&organisation, &t.name, &t.slug, &user_token))
+            .collect();
+        let teams = teams?;
+
+        let repos: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| RepoStatus {
+                repo: r.name.clone(),
+                result: github::get_collaborators(r, "all", &user_token).map(|collaborators| {
+                    collaborators
+                        .iter()
+                        .map(|c| (c.login.clone(), c.permissions.highest().to_string()))
+                        .collect()
+                }),
+            })
+            .collect();
+
+        let accesses: Vec<_> = repos
+            .iter()
+            .filter_map(|r| match &r.result {
+                Ok(collaborators) => Some(RepoAccess {
+                    repo: r.repo.clone(),
+                    collaborators: collaborators.clone(),
+                }),
+                Err(_) => None,
+            })
+            .collect();
+
+        let matrix = AccessMatrix::build(&accesses, &members, &owners, &teams);
+        matrix.print();
+
+        if let Some(path) = &self.csv {
+            write(path, matrix.to_csv())
+                .with_context(|| format!("Cannot write csv file {:?}", path))?;
+            println!("\nWrote access matrix to {:?}", path);
+        }
+
+        print_errors(&repos);
+
+        Ok(())
+    }
+}
+
+fn fetch_team(org: &str, name: &str, slug: &str, token: &str) -> Result<TeamAccess> {
+    log::info!("Fetching team {}", slug);
+
+    let members = github::get_team_members(org, slug, "all", token)?;
+    let repos = github::get_team_repos(org, slug, token)?
+        .iter()
+        .map(|r| (r.name.clone(), r.permission().to_string()))
+        .collect();
+
+    Ok(TeamAccess {
+        name: name.to_string(),
+        members,
+        repos,
+    })
+}
+
+struct TeamAccess {
+    name: String,
+    members: Vec<String>,
+    /// Repository name to permission
+    repos: BTreeMap<String, String>,
+}
+
+struct RepoAccess {
+    repo: String,
+    /// Login to permission
+    collaborators: BTreeMap<String, String>,
+}
+
+struct RepoStatus {
+    repo: String,
+    result: Result<BTreeMap<String, String>, Error>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Team,
+    Owner,
+    Member,
+    Outside,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Team => "team",
+            Kind::Owner => "owner",
+            Kind::Member => "member",
+            Kind::Outside => "outside collaborator",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct AccessRow {
+    name: String,
+    kind: Kind,
+    /// Permission on each repository of the matrix, in the same order
+    permissions: Vec<Option<String>>,
+    /// Repositories with admin permission that doesn't come from one of the user's teams
+    flagged: Vec<String>,
+}
+
+struct AccessMatrix {
+    repos: Vec<String>,
+    rows: Vec<AccessRow>,
+}
+
+fn rank(permission: &str) -> usize {
+    match permission {
+        "admin" => 5,
+        "maintain" => 4,
+        "push" => 3,
+        "triage" => 2,
+        _ => 1,
+    }
+}
+
+impl AccessMatrix {
+    fn build(
+        repos: &[RepoAccess],
+        members: &[String],
+        owners: &[String],
+        teams: &[TeamAccess],
+    ) -> AccessMatrix {
+        let mut rows = vec![];
+
+        for team in teams {
+            let permissions: Vec<_> = repos.iter().map(|r| team.repos.get(&r.repo)).collect();
+            if permissions.iter().all(|p| p.is_none()) {
+                continue;
+            }
+            rows.push(AccessRow {
+                name: team.name.clone(),
+                kind: Kind::Team,
+                permissions: permissions.into_iter().map(|p| p.cloned()).collect(),
+                flagged: vec![],
+            });
+        }
+
+        let users: BTreeSet<_> = members
+            .iter()
+            .chain(repos.iter().flat_map(|r| r.collaborators.keys()))
+            .collect();
+
+        for user in users {
+            let kind = if owners.contains(user) {
+                Kind::Owner
+            } else if members.contains(user) {
+                Kind::Member
+            } else {
+                Kind::Outside
+            };
+            let user_teams: Vec<_> = teams.iter().filter(|t| t.members.contains(user)).collect();
+
+            let mut permissions = vec![];
+            let mut flagged = vec![];
+            for repo in repos {
+                let team_permissions: Vec<_> = user_teams
+                    .iter()
+                    .filter_map(|t| t.repos.get(&repo.repo))
+                    .collect();
+                let permission = repo
+                    .collaborators
+                    .get(user)
+                    .into_iter()
+                    .chain(team_permissions.iter().cloned())
+                    .max_by_key(|p| rank(p))
+                    .cloned();
+
+                if permission.as_deref() == Some("admin")
+                    && kind != Kind::Owner
+                    && !team_permissions.iter().any(|p| p.as_str() == "admin")
+                {
+                    flagged.push(repo.repo.clone());
+                }
+                permissions.push(permission);
+            }
+
+            rows.push(AccessRow {
+                name: user.clone(),
+                kind,
+                permissions,
+                flagged,
+            });
+        }
+
+        AccessMatrix {
+            repos: repos.iter().map(|r| r.repo.clone()).collect(),
+            rows,
+        }
+    }
+
+    fn print(&self) {
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+
+        let mut titles = vec![cell!("Name"), cell!("Type")];
+        titles.extend(self.repos.iter().map(|r| cell!(c -> r)));
+        table.set_titles(Row::new(titles));
+
+        for access in &self.rows {
+            let mut cells = vec![cell!(b -> &access.name), cell!(access.kind.name())];
+            for (repo, permission) in self.repos.iter().zip(&access.permissions) {
+                let cell = match permission {
+                    Some(p) if access.flagged.contains(repo) => cell!(Frc -> format!("{}!", p)),
+                    Some(p) => cell!(c -> p),
+                    None => Cell::new(""),
+                };
+                cells.push(cell);
+            }
+            table.add_row(Row::new(cells));
+        }
+
+        table.printstd();
+
+        let flagged: Vec<_> = self.rows.iter().filter(|r| !r.flagged.is_empty()).collect();
+        if flagged.is_empty() {
+            println!("\nThere is no user with admin permission outside their teams");
+        } else {
+            let msg = format!(
+                "{} users have admin permission on repos outside their teams:",
+                flagged.len()
+            );
+            println!("\n{}\n", msg.red());
+            for row in flagged {
+                println!("{}: {}", row.name.bold(), row.flagged.join(", "));
+            }
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut header = vec!["name".to_string(), "type".to_string()];
+        header.extend(self.repos.iter().cloned());
+        header.push("admin_outside_teams".to_string());
+
+        let mut lines = vec![to_csv_line(&header)];
+        for access in &self.rows {
+            let mut fields = vec![access.name.clone(), access.kind.name().to_string()];
+            fields.extend(
+                access
+                    .permissions
+                    .iter()
+                    .map(|p| p.clone().unwrap_or_default()),
+            );
+            fields.push(access.flagged.join(";"));
+            lines.push(to_csv_line(&fields));
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+fn to_csv_line(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|f| {
+            if f.contains(&[',', '"', '\n'][..]) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn print_errors(repos: &[RepoStatus]) {
+    let errors: Vec<_> = repos.iter().filter(|r| r.result.is_err()).collect();
+    if errors.is_empty() {
+        return;
+    }
+
+    let msg = format!(
+        "There are {} repos that are left out because of errors:",
+        errors.len()
+    );
+    println!("\n{}\n", msg.red());
+
+    let mut error_table = Table::new();
+    error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    error_table.set_titles(row!["Repo", "Error"]);
+    for error in errors {
+        if let Err(e) = &error.result {
+            let msg = format!("{:?}", e);
+            let lines = common::sub_strings(msg.as_str(), 80);
+            let lines = lines.join("\n");
+            error_table.add_row(row!(cell!(b -> &error.repo), cell!(Fr -> lines.as_str())));
+        }
+    }
+    error_table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_matrix() {
+        let repos = vec![
+            RepoAccess {
+                repo: "lang-sma".to_string(),
+                collaborators: vec![
+                    ("anna".to_string(), "admin".to_string()),
+                    ("bo".to_string(), "admin".to_string()),
+                    ("owner".to_string(), "admin".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            },
+            RepoAccess {
+                repo: "lang-sme".to_string(),
+                collaborators: vec![
+                    ("anna".to_string(), "push".to_string()),
+                    ("ext".to_string(), "pull".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            },
+        ];
+        let teams = vec![TeamAccess {
+            name: "Linguists".to_string(),
+            members: vec!["anna".to_string()],
+            repos: vec![
+                ("lang-sma".to_string(), "admin".to_string()),
+                ("lang-sme".to_string(), "push".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        }];
+
+        let members = vec!["anna".to_string(), "bo".to_string(), "owner".to_string()];
+        let owners = vec!["owner".to_string()];
+
+        let matrix = AccessMatrix::build(&repos, &members, &owners, &teams);
+
+        let some = |p: &str| Some(p.to_string());
+        assert_eq!(
+            matrix.repos,
+            vec!["lang-sma".to_string(), "lang-sme".to_string()]
+        );
+        assert_eq!(
+            matrix.rows,
+            vec![
+                AccessRow {
+                    name: "Linguists".to_string(),
+                    kind: Kind::Team,
+                    permissions: vec![some("admin"), some("push")],
+                    flagged: vec![],
+                },
+                AccessRow {
+                    name: "anna".to_string(),
+                    kind: Kind::Member,
+                    permissions: vec![some("admin"), some("push")],
+                    flagged: vec![],
+                },
+                AccessRow {
+                    name: "bo".to_string(),
+                    kind: Kind::Member,
+                    permissions: vec![some("admin"), None],
+                    flagged: vec!["lang-sma".to_string()],
+                },
+                AccessRow {
+                    name: "ext".to_string(),
+                    kind: Kind::Outside,
+                    permissions: vec![None, some("pull")],
+                    flagged: vec![],
+                },
+                AccessRow {
+                    name: "owner".to_string(),
+                    kind: Kind::Owner,
+                    permissions: vec![some("admin"), None],
+                    flagged: vec![],
+                },
+            ]
+        );
+
+        assert_eq!(
+            matrix.to_csv().lines().nth(3),
+            Some("bo,member,admin,,lang-sma")
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use super::common;
+use crate::filter::Filter;
+use crate::github;
+use crate::github::RepoSettings;
+use anyhow::{Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show merge options and features of all repositories that match a pattern
+pub struct ShowSettingsArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+}
+
+impl ShowSettingsArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let filtered_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user_token)?;
+
+        if filtered_repos.is_empty() {
+            println!(
+                "There is no repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let mut statuses: Vec<_> = filtered_repos
+            .par_iter()
+            .map(|r| Status {
+                repo: r.name.clone(),
+                result: github::get_repo_settings(r, &user_token),
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<RepoSettings, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Option<Row> {
+        let settings = self.result.as_ref().ok()?;
+        let values = [
+            settings.allow_squash_merge,
+            settings.allow_merge_commit,
+            settings.allow_rebase_merge,
+            settings.allow_auto_merge,
+            settings.delete_branch_on_merge,
+            settings.has_issues,
+            settings.has_wiki,
+            settings.has_projects,
+            settings.has_discussions,
+            settings.is_template,
+            settings.web_commit_signoff_required,
+        ];
+
+        let mut cells = vec![cell!(b -> &self.repo)];
+        cells.extend(values.iter().map(|v| to_cell(*v)));
+        Some(Row::new(cells))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+/// A setting that GitHub does not return, for example auto-merge without admin access, is shown as -
+fn to_cell(value: Option<bool>) -> Cell {
+    match value {
+        Some(true) => cell!(Fgc -> "yes"),
+        Some(false) => cell!(Frc -> "no"),
+        None => cell!(c -> "-"),
+    }
+}
+
+fn summarize(statuses: &[Status]) {
+    let rows: Vec<_> = statuses.iter().filter_map(|s| s.to_row()).collect();
+
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row![
+        "Repo",
+        "Squash",
+        "Merge",
+        "Rebase",
+        "Auto-merge",
+        "Delete branch",
+        "Issues",
+        "Wiki",
+        "Projects",
+        "Discussions",
+        "Template",
+        "Signoff"
+    ]);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    if !errors.is_empty() {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
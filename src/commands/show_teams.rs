@@ -0,0 +1,183 @@
+use super::common;
+use super::team_helper::TeamMembership;
+use crate::filter::{Filter, Filterable};
+use crate::github;
+use crate::github::Team;
+use anyhow::{Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Row, Table};
+use rayon::prelude::*;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show members and repositories of all teams that match a pattern
+pub struct ShowTeamsArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter teams by their slug
+    pub regex: Option<Filter>,
+    #[structopt(long)]
+    /// Option to only show the hierarchy of parent and child teams
+    pub tree: bool,
+}
+
+impl ShowTeamsArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let teams = github::get_teams(&organisation, &user_token)?;
+        let mut teams = <Team as Filterable>::filter_with_option(teams, self.regex.as_ref());
+        teams.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        if teams.is_empty() {
+            println!(
+                "There is no teams in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        if self.tree {
+            print_tree(&teams);
+            return Ok(());
+        }
+
+        let details: Vec<_> = teams
+            .par_iter()
+            .map(|t| TeamDetail {
+                team: t.name.clone(),
+                result: fetch(&organisation, &t.slug, &user_token),
+            })
+            .collect();
+
+        summarize(&details);
+
+        Ok(())
+    }
+}
+
+/// Print teams under their parent, a team whose parent is filtered out is shown at the top level
+fn print_tree(teams: &[Team]) {
+    let is_root = |t: &Team| match &t.parent {
+        Some(parent) => !teams.iter().any(|p| p.id == parent.id),
+        None => true,
+    };
+
+    for team in teams.iter().filter(|t| is_root(t)) {
+        print_subtree(teams, team, 0);
+    }
+}
+
+fn print_subtree(teams: &[Team], team: &Team, depth: usize) {
+    println!("{}{} ({})", "  ".repeat(depth), team.name.bold(), team.slug);
+
+    let children = teams
+        .iter()
+        .filter(|t| t.parent.as_ref().map(|p| p.id) == Some(team.id));
+    for child in children {
+        print_subtree(teams, child, depth + 1);
+    }
+}
+
+struct Members {
+    maintainers: Vec<String>,
+    members: Vec<String>,
+    /// Repository name and permission
+    repos: Vec<(String, String)>,
+}
+
+fn fetch(org: &str, slug: &str, token: &str) -> Result<Members> {
+    log::info!("Fetching team {}", slug);
+
+    let TeamMembership {
+        members,
+        maintainers,
+    } = TeamMembership::fetch(org, slug, token)?;
+    let mut repos: Vec<_> = github::get_team_repos(org, slug, token)?
+        .iter()
+        .map(|r| (r.name.clone(), r.permission().to_string()))
+        .collect();
+    repos.sort();
+
+    Ok(Members {
+        maintainers,
+        members,
+        repos,
+    })
+}
+
+struct TeamDetail {
+    team: String,
+    result: Result<Members, Error>,
+}
+
+impl TeamDetail {
+    fn to_row(&self) -> Option<Row> {
+        let detail = self.result.as_ref().ok()?;
+
+        let members: Vec<_> = detail
+            .maintainers
+            .iter()
+            .map(|m| format!("{} (maintainer)", m))
+            .chain(detail.members.iter().cloned())
+            .collect();
+        let repos: Vec<_> = detail
+            .repos
+            .iter()
+            .map(|(repo, permission)| format!("{}: {}", repo, permission))
+            .collect();
+
+        Some(row!(
+            b -> &self.team,
+            members.join("\n"),
+            repos.join("\n")
+        ))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.team), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn summarize(details: &[TeamDetail]) {
+    let rows: Vec<_> = details.iter().filter_map(|d| d.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Team", "Members", "Repos"]);
+    table.printstd();
+
+    let errors: Vec<_> = details.iter().filter(|d| d.has_error()).collect();
+    if !errors.is_empty() {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Team", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -25,6 +25,7 @@ impl ShowUsersArgs {
     pub fn run(&self) -> Result<()> {
         let user_token = common::user_token()?;
         let organisation = common::organisation(self.organisation.as_deref())?;
+        common::ensure_organisation(&organisation, &user_token)?;
 
         let result = github::get_org_members(&organisation, &user_token);
 
@@ -0,0 +1,22 @@
+use super::snapshot_restore::*;
+use super::snapshot_save::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Save or restore the HEAD commits of all local repositories that match a pattern
+pub enum SnapshotArgs {
+    #[structopt(name = "save")]
+    Save(SnapshotSaveArgs),
+    #[structopt(name = "restore")]
+    Restore(SnapshotRestoreArgs),
+}
+
+impl SnapshotArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            SnapshotArgs::Save(args) => args.run(),
+            SnapshotArgs::Restore(args) => args.run(),
+        }
+    }
+}
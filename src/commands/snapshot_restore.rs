@@ -0,0 +1,254 @@
+use super::common;
+use super::models::{RepoSnapshot, Snapshot};
+use crate::filter::{Filter, Filterable};
+use crate::git;
+use crate::git::{Clonable, GitCredential, GitRepo};
+use crate::path;
+use crate::user::User;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use git2::{BranchType, Oid};
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Checkout all repositories in a lock file at the saved commits
+///
+/// A repository is checked out on its saved branch if that branch points at the saved commit,
+/// otherwise HEAD will be detached at the saved commit.
+///
+/// Missing repositories will be cloned.
+///
+/// This will refuse to restore if there are repositories with uncommitted changes
+pub struct SnapshotRestoreArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// Default is the organisation saved in the lock file.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Option to always detach HEAD at the saved commits
+    pub detach: bool,
+    #[structopt(parse(from_os_str))]
+    /// Path to the lock file
+    pub file: PathBuf,
+}
+
+impl SnapshotRestoreArgs {
+    pub fn run(&self) -> Result<()> {
+        let user = common::user()?;
+        let root = common::root()?;
+
+        let snapshot = Snapshot::get(&self.file)
+            .with_context(|| format!("Cannot read snapshot file {:?}", self.file))?;
+        let organisation = self
+            .organisation
+            .clone()
+            .unwrap_or_else(|| snapshot.organisation.clone());
+
+        let repos = RepoSnapshot::filter_with_option(snapshot.repos, self.regex.as_ref());
+
+        if repos.is_empty() {
+            println!(
+                "There is no repositories in the snapshot that matches pattern {:?}",
+                self.regex
+            );
+            return Ok(());
+        }
+
+        let dirty: Vec<_> = repos
+            .iter()
+            .filter(|r| is_dirty(&path::local_path_repo(&organisation, &r.name, &root)))
+            .collect();
+
+        if !dirty.is_empty() {
+            println!("The following repos have uncommitted changes:");
+            for repo in dirty {
+                println!("{}", repo.name);
+            }
+            return Err(anyhow!(
+                "Refuse to restore the snapshot. Please commit or stash the changes first."
+            ));
+        }
+
+        let statuses: Vec<_> = repos
+            .par_iter()
+            .map(|r| restore(r, &organisation, &root, &user, self.detach))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+fn is_dirty(dir: &PathBuf) -> bool {
+    if !dir.exists() {
+        return false;
+    }
+
+    match git::open(dir) {
+        Ok(git_repo) => match git::status(&git_repo, false) {
+            Ok(status) => status.has_tracked_changes(),
+            Err(_) => false,
+        },
+        Err(_) => false,
+    }
+}
+
+fn restore(repo: &RepoSnapshot, org: &str, root: &str, user: &User, detach: bool) -> Status {
+    log::info!("Processing repo {}", repo.name);
+
+    let mut cloned = false;
+
+    let mut restore = || -> Result<Checkout> {
+        let local_path = path::local_path_repo(org, &repo.name, root);
+        let cred = GitCredential::from(user);
+
+        if !local_path.exists() {
+            let remote_url = repo
+                .url
+                .clone()
+                .ok_or_else(|| anyhow!("There is no origin url to clone this repository"))?;
+            let git_repo = GitRepo {
+                remote_url,
+                local_path: local_path.clone(),
+                cred: Some(cred.clone()),
+            };
+            git_repo.gclone()?;
+            cloned = true;
+        }
+
+        let git_repo = git::open(&local_path)
+            .with_context(|| format!("{:?} is not a git directory.", local_path))?;
+
+        let oid = Oid::from_str(&repo.sha)?;
+        if git_repo.find_commit(oid).is_err() {
+            git::fetch_all(&git_repo, "origin", Some(cred))?;
+        }
+        let commit = git_repo
+            .find_commit(oid)
+            .with_context(|| format!("Cannot find commit {}", repo.sha))?;
+
+        if let (Some(branch), false) = (&repo.branch, detach) {
+            match git_repo.find_branch(branch, BranchType::Local) {
+                Ok(b) if b.get().target() == Some(oid) => {
+                    git::checkout_local_branch(&git_repo, branch)?;
+                    return Ok(Checkout::Branch(branch.to_string()));
+                }
+                Err(_) => {
+                    git_repo.branch(branch, &commit, false)?;
+                    git::checkout_local_branch(&git_repo, branch)?;
+                    return Ok(Checkout::Branch(branch.to_string()));
+                }
+                Ok(_) => {}
+            }
+        }
+
+        git::checkout_detached(&git_repo, &repo.sha)?;
+        Ok(Checkout::Detached)
+    };
+
+    let result = restore();
+
+    Status {
+        repo: repo.name.clone(),
+        sha: repo.sha.clone(),
+        cloned,
+        result,
+    }
+}
+
+enum Checkout {
+    Branch(String),
+    Detached,
+}
+
+struct Status {
+    repo: String,
+    sha: String,
+    cloned: bool,
+    result: Result<Checkout, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        let short_sha = self.sha.get(..8).unwrap_or(&self.sha);
+        let cloned = if self.cloned { "Cloned" } else { "-" };
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            cell!(short_sha),
+            self.result_to_cell(),
+            cell!(r -> cloned),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(Checkout::Branch(b)) => cell!(Fgl -> b),
+            Ok(Checkout::Detached) => cell!(Fyl -> "(detached)"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Commit", "Checkout", "Clone"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!("\nRestored {} repos successfully!", successes.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
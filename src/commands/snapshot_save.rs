@@ -0,0 +1,132 @@
+use super::common;
+use super::models::{RepoSnapshot, Snapshot};
+use crate::filter::Filter;
+use crate::git;
+use crate::path;
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use prettytable::{cell, format, row, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Save branch and HEAD commit of all local repositories that match a pattern to a lock file
+///
+/// This will refuse to save if there are repositories with uncommitted changes
+pub struct SnapshotSaveArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(parse(from_os_str))]
+    /// Path to the lock file
+    pub file: PathBuf,
+}
+
+impl SnapshotSaveArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} that matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let results: Vec<_> = sub_dirs.par_iter().map(snapshot).collect();
+
+        let errors: Vec<_> = results
+            .iter()
+            .filter_map(|(name, r)| r.as_ref().err().map(|e| (name, e)))
+            .collect();
+
+        if !errors.is_empty() {
+            let msg = format!(
+                "There are {} errors when executing the command:",
+                errors.len()
+            );
+            println!("\n{}\n", msg.red());
+
+            let mut error_table = Table::new();
+            error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+            error_table.set_titles(row!["Repo", "Error"]);
+            for (name, e) in errors {
+                let msg = format!("{:?}", e);
+                let lines = common::sub_strings(msg.as_str(), 80);
+                let lines = lines.join("\n");
+                error_table.add_row(row!(cell!(b -> name), cell!(Fr -> lines.as_str())));
+            }
+            error_table.printstd();
+
+            return Err(anyhow!(
+                "Snapshot is not saved. Please fix the errors above first."
+            ));
+        }
+
+        let repos: Vec<_> = results.into_iter().filter_map(|(_, r)| r.ok()).collect();
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row!["Repo", "Branch", "Commit"]);
+        for repo in &repos {
+            table.add_row(to_row(repo));
+        }
+        table.printstd();
+
+        let snapshot = Snapshot {
+            organisation,
+            repos,
+        };
+        snapshot.save(&self.file)?;
+
+        let msg = format!(
+            "\nSaved snapshot of {} repos to {:?}",
+            snapshot.repos.len(),
+            self.file
+        );
+        println!("{}", msg.green());
+
+        Ok(())
+    }
+}
+
+fn snapshot(dir: &PathBuf) -> (String, Result<RepoSnapshot>) {
+    let mut dir_name = "".to_string();
+
+    let mut snapshot = || -> Result<RepoSnapshot> {
+        dir_name = path::dir_name(dir)?;
+
+        let git_repo =
+            git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+        let status = git::status(&git_repo, false)?;
+        if status.has_tracked_changes() {
+            return Err(anyhow!(
+                "Repository has uncommitted changes. Please commit or stash them first."
+            ));
+        }
+
+        RepoSnapshot::from_repo(&dir_name, &git_repo)
+    };
+
+    let result = snapshot();
+    (dir_name, result)
+}
+
+fn to_row(repo: &RepoSnapshot) -> Row {
+    row![
+        b -> &repo.name,
+        repo.branch.as_deref().unwrap_or("(detached)"),
+        repo.sha
+    ]
+}
@@ -0,0 +1,26 @@
+use super::stash_drop::*;
+use super::stash_list::*;
+use super::stash_pop::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// List, pop or drop stashes of all local repositories that match a pattern
+pub enum StashArgs {
+    #[structopt(name = "drop")]
+    Drop(StashDropArgs),
+    #[structopt(name = "list")]
+    List(StashListArgs),
+    #[structopt(name = "pop")]
+    Pop(StashPopArgs),
+}
+
+impl StashArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            StashArgs::Drop(args) => args.run(),
+            StashArgs::List(args) => args.run(),
+            StashArgs::Pop(args) => args.run(),
+        }
+    }
+}
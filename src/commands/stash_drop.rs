@@ -0,0 +1,95 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::path;
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Drop a stash in all local repositories that match a pattern
+///
+/// It'll show a confirmation prompt and you have to enter 'YES' to confirm your action
+pub struct StashDropArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short, default_value = "0")]
+    /// Index of the stash
+    pub index: usize,
+}
+
+impl StashDropArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        let stashed: Vec<_> = sub_dirs
+            .par_iter()
+            .filter_map(|d| stash_message(d, self.index).map(|m| (d, m)))
+            .collect();
+
+        if stashed.is_empty() {
+            println!("There is no stash@{{{}}} to drop!", self.index);
+            return Ok(());
+        }
+
+        if !confirm(&stashed, self.index)? {
+            println!("Command is aborted. Nothing got dropped!");
+            return Ok(());
+        }
+
+        for (dir, _) in stashed {
+            match drop_stash(dir, self.index) {
+                Ok(name) => println!("Dropped stash of repo {} successfully", name),
+                Err(e) => println!("Failed to drop stash of repo {:?} because {:?}", dir, e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Message of the stash at index, None if there is no such stash
+fn stash_message(dir: &PathBuf, index: usize) -> Option<String> {
+    let mut git_repo = git::open(dir).ok()?;
+    let entries = git::stash_list(&mut git_repo).ok()?;
+    entries
+        .into_iter()
+        .find(|e| e.index == index)
+        .map(|e| e.message)
+}
+
+fn confirm(stashed: &[(&PathBuf, String)], index: usize) -> Result<bool> {
+    println!("The following stashes will be dropped:");
+
+    for (dir, message) in stashed {
+        println!("{}: stash@{{{}}} {}", path::dir_name(dir)?, index, message);
+    }
+
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to drop {} stash(es)?\nEnter {} to continue",
+            stashed.len(),
+            key
+        ),
+        key,
+    )
+}
+
+fn drop_stash(dir: &PathBuf, index: usize) -> Result<String> {
+    let dir_name = path::dir_name(dir)?;
+    let mut git_repo =
+        git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+    git::stash_drop(&mut git_repo, index)?;
+    Ok(dir_name)
+}
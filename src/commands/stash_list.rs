@@ -0,0 +1,82 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::git::StashEntry;
+use crate::path;
+use anyhow::{Context, Result};
+use prettytable::{cell, format, row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// List stashes of all local repositories that match a pattern
+pub struct StashListArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+}
+
+impl StashListArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        let results: Vec<_> = sub_dirs.par_iter().map(list).collect();
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        table.set_titles(row!["Repo", "Stash", "Message"]);
+
+        let mut count = 0;
+        for (name, result) in &results {
+            match result {
+                Ok(entries) => {
+                    for entry in entries {
+                        table.add_row(row![
+                            b -> name,
+                            format!("stash@{{{}}}", entry.index),
+                            entry.message
+                        ]);
+                        count += 1;
+                    }
+                }
+                Err(e) => println!("Failed to list stashes of repo {} because {:?}", name, e),
+            }
+        }
+
+        if count == 0 {
+            println!(
+                "There is no stash in local repositories of organisation {} that match pattern {:?}",
+                organisation, self.regex
+            );
+        } else {
+            table.printstd();
+        }
+
+        Ok(())
+    }
+}
+
+fn list(dir: &PathBuf) -> (String, Result<Vec<StashEntry>>) {
+    let mut dir_name = "".to_string();
+
+    let mut list = || -> Result<Vec<StashEntry>> {
+        dir_name = path::dir_name(dir)?;
+
+        let mut git_repo =
+            git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+        git::stash_list(&mut git_repo)
+    };
+
+    let result = list();
+    (dir_name, result)
+}
@@ -0,0 +1,162 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::path;
+use anyhow::{Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Apply a stash and remove it from the stash list in all local repositories that match a pattern
+///
+/// A stash that cannot be applied cleanly is kept in the stash list
+pub struct StashPopArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short, default_value = "0")]
+    /// Index of the stash
+    pub index: usize,
+}
+
+impl StashPopArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = sub_dirs.par_iter().map(|d| pop(d, self.index)).collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+fn pop(dir: &PathBuf, index: usize) -> Status {
+    let mut dir_name = "".to_string();
+
+    let mut pop = || -> Result<bool> {
+        dir_name = path::dir_name(dir)?;
+
+        let mut git_repo =
+            git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+        if git::stash_list(&mut git_repo)?.len() <= index {
+            return Ok(false);
+        }
+
+        git::stash_pop(&mut git_repo, index)
+            .context("Stash is kept because it cannot be applied")?;
+        Ok(true)
+    };
+
+    let result = pop();
+    Status {
+        repo: dir_name,
+        result,
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<bool, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.repo), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(true) => cell!(Fgr -> "Popped"),
+            Ok(false) => cell!(r -> "-"),
+            Err(_) => cell!(Frr -> "Failed"),
+        }
+    }
+
+    fn is_popped(&self) -> bool {
+        matches!(self.result, Ok(true))
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn summarize(statuses: &[Status]) {
+    let statuses: Vec<_> = statuses
+        .iter()
+        .filter(|s| s.is_popped() || s.has_error())
+        .collect();
+
+    if statuses.is_empty() {
+        println!("There is no stash to pop!");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Stash"]);
+    for status in &statuses {
+        table.add_row(status.to_row());
+    }
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let popped: Vec<_> = statuses.iter().filter(|s| s.is_popped()).collect();
+
+    if !popped.is_empty() {
+        let msg = format!("\nPopped stash in {} repos successfully!", popped.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
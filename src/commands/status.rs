@@ -4,8 +4,10 @@ use crate::git;
 use crate::git::GitStatus;
 use crate::path::dir_name;
 use anyhow::{Context, Result};
+use git2::{BranchType, ErrorCode, Repository};
 use prettytable::{cell, format, row, Row, Table};
 use rayon::prelude::*;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -22,10 +24,17 @@ pub struct StatusArgs {
     pub regex: Option<Filter>,
     #[structopt(long, short)]
     /// Option to show more detail
+    ///
+    /// This will also show the upstream branch and the number of stashes of each repository
     pub verbose: bool,
     #[structopt(long, short)]
     /// Option to omit repositories without changes
     pub quiet: bool,
+    #[structopt(long)]
+    /// Show ahead/behind against this revision instead of the upstream, e.g. origin/main
+    ///
+    /// Without a value, the default branch of each repository on GitHub is used.
+    pub against: Option<Option<String>>,
 }
 
 impl StatusArgs {
@@ -35,12 +44,34 @@ impl StatusArgs {
 
         let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
 
-        let statuses: Result<Vec<_>> = sub_dirs.iter().map(|d| status(&d)).collect();
+        let against = match &self.against {
+            None => Against::Upstream,
+            Some(Some(revision)) => Against::Revision(revision.to_string()),
+            Some(None) => {
+                let token = common::user_token()?;
+                let remote_repos = common::query_and_filter_repositories(
+                    &organisation,
+                    self.regex.as_ref(),
+                    &token,
+                )?;
+                let default_branches = remote_repos
+                    .into_iter()
+                    .filter_map(|r| {
+                        let branch = r.default_branch?;
+                        Some((r.name, format!("origin/{}", branch)))
+                    })
+                    .collect();
+                Against::DefaultBranch(default_branches)
+            }
+        };
+
+        let statuses: Result<Vec<_>> = sub_dirs.iter().map(|d| status(&d, &against)).collect();
         let statuses: Vec<_> = statuses?;
         let statuses: Vec<_> = statuses
             .into_iter()
             .filter(|status| {
                 !(self.quiet
+                    && status.state.is_none()
                     && status.status.is_empty()
                     && status.status.is_ahead == 0
                     && status.status.is_behind == 0)
@@ -48,33 +79,119 @@ impl StatusArgs {
             .collect();
 
         let rows = to_rows(&statuses, self.verbose);
-        let table = to_table(&rows);
+        let table = to_table(&rows, &against);
 
         table.printstd();
         Ok(())
     }
 }
 
-fn status(dir: &PathBuf) -> Result<RepoStatus> {
+/// What ahead/behind is computed against
+enum Against {
+    Upstream,
+    Revision(String),
+    /// Default branch of each repository by name
+    DefaultBranch(HashMap<String, String>),
+}
+
+impl Against {
+    fn revision(&self, repo_name: &str) -> Option<&str> {
+        match self {
+            Against::Upstream => None,
+            Against::Revision(revision) => Some(revision),
+            Against::DefaultBranch(map) => map.get(repo_name).map(|b| b.as_str()),
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            Against::Upstream => "±origin".to_string(),
+            Against::Revision(revision) => format!("±{}", revision),
+            Against::DefaultBranch(_) => "±default".to_string(),
+        }
+    }
+}
+
+fn status(dir: &PathBuf, against: &Against) -> Result<RepoStatus> {
     let name = dir_name(dir)?;
-    let git_repo = git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
 
-    let status = git::status(&git_repo, false)?;
-    let branch = git::head_shorthand(&git_repo)?;
+    let mut git_repo =
+        git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+    let mut status = git::status(&git_repo, false)?;
+    let mut diverged_unknown = false;
+    if !matches!(against, Against::Upstream) {
+        match against
+            .revision(&name)
+            .and_then(|r| git::ahead_behind(&git_repo, r))
+        {
+            Some((ahead, behind)) => {
+                status.is_ahead = ahead;
+                status.is_behind = behind;
+            }
+            None => {
+                status.is_ahead = 0;
+                status.is_behind = 0;
+                diverged_unknown = true;
+            }
+        }
+    }
+    let (branch, head_state) = head(&git_repo)?;
+
+    let template_applying = git_repo.path().join("gut/template_apply/APPLYING").exists();
+    let state = if template_applying {
+        Some("template-applying")
+    } else {
+        git::operation_in_progress(&git_repo).or(head_state)
+    };
+
+    let upstream = git_repo
+        .find_branch(&branch, BranchType::Local)
+        .and_then(|b| b.upstream())
+        .ok()
+        .and_then(|u| u.name().ok().flatten().map(|n| n.to_string()));
+    let stash_count = git::stash_list(&mut git_repo)?.len();
+
     let repo_status = RepoStatus {
         name,
         branch,
+        state,
+        upstream,
+        stash_count,
+        diverged_unknown,
         status,
     };
     Ok(repo_status)
 }
 
-fn to_table(statuses: &[StatusRow]) -> Table {
+/// Name of the current branch, or short sha of HEAD if it is detached
+///
+/// The second value tells if HEAD is detached or the branch is unborn
+fn head(repo: &Repository) -> Result<(String, Option<&'static str>)> {
+    match repo.head() {
+        Ok(_) if repo.head_detached()? => {
+            let sha = git::head_sha(repo)?;
+            Ok((sha[..8].to_string(), Some("detached")))
+        }
+        Ok(_) => Ok((git::head_shorthand(repo)?, None)),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => {
+            let head_ref = repo.find_reference("HEAD")?;
+            let branch = head_ref
+                .symbolic_target()
+                .map(|t| t.trim_start_matches("refs/heads/").to_string())
+                .unwrap_or_else(|| "HEAD".to_string());
+            Ok((branch, Some("unborn")))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn to_table(statuses: &[StatusRow], against: &Against) -> Table {
     let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
     let mut table = Table::init(rows);
     table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
     table.set_titles(
-        row!["Repo", "branch", r -> "±origin", r -> "U", r -> "D", r -> "M", r -> "C", r -> "A"],
+        row!["Repo", "branch", "State", r -> against.title(), r -> "U", r -> "D", r -> "M", r -> "C", r -> "A"],
     );
     table
 }
@@ -94,6 +211,7 @@ fn to_total_summarize(statuses: &[RepoStatus]) -> Vec<StatusRow> {
     let total = statuses.len().to_string();
     let mut unpushed_repo_count: usize = 0;
     let mut uncommited_repo_count: usize = 0;
+    let mut in_progress_repo_count: usize = 0;
     let mut total_unadded: usize = 0;
     let mut total_deleted: usize = 0;
     let mut total_modified: usize = 0;
@@ -104,6 +222,9 @@ fn to_total_summarize(statuses: &[RepoStatus]) -> Vec<StatusRow> {
         if !status.status.is_empty() {
             uncommited_repo_count += 1;
         }
+        if status.state.is_some() {
+            in_progress_repo_count += 1;
+        }
         if status.status.is_ahead > 0 || status.status.is_behind > 0 {
             unpushed_repo_count += 1;
         }
@@ -118,6 +239,7 @@ fn to_total_summarize(statuses: &[RepoStatus]) -> Vec<StatusRow> {
         total,
         unpushed_repo_count: unpushed_repo_count.to_string(),
         uncommited_repo_count: uncommited_repo_count.to_string(),
+        in_progress_repo_count: in_progress_repo_count.to_string(),
         total_unadded: total_unadded.to_string(),
         total_deleted: total_deleted.to_string(),
         total_modified: total_modified.to_string(),
@@ -132,6 +254,11 @@ fn to_total_summarize(statuses: &[RepoStatus]) -> Vec<StatusRow> {
 struct RepoStatus {
     name: String,
     branch: String,
+    state: Option<&'static str>,
+    upstream: Option<String>,
+    stash_count: usize,
+    /// The revision to compare against cannot be resolved
+    diverged_unknown: bool,
     status: GitStatus,
 }
 
@@ -147,6 +274,14 @@ impl RepoStatus {
     fn to_repo_detail(&self) -> Vec<StatusRow> {
         let mut rows = vec![];
         rows.push(self.to_repo_summarize());
+        rows.push(StatusRow::Info {
+            key: "upstream".to_string(),
+            value: self.upstream.clone().unwrap_or_else(|| "-".to_string()),
+        });
+        rows.push(StatusRow::Info {
+            key: "stashes".to_string(),
+            value: self.stash_count.to_string(),
+        });
         rows.append(&mut show_detail_changes("C", &self.status.conflicted));
         rows.append(&mut show_detail_changes("U", &self.status.new));
         rows.append(&mut show_detail_changes("D", &self.status.deleted));
@@ -160,7 +295,12 @@ impl RepoStatus {
         StatusRow::RepoSummarize {
             name: self.name.to_string(),
             branch: self.branch.to_string(),
-            ahead_behind: self.status.ahead_behind(),
+            state: self.state.unwrap_or("-").to_string(),
+            ahead_behind: if self.diverged_unknown {
+                "?".to_string()
+            } else {
+                self.status.ahead_behind()
+            },
             unadded: self.status.new.len().to_string(),
             deleted: self.status.deleted.len().to_string(),
             modified: self.status.modified.len().to_string(),
@@ -189,6 +329,7 @@ enum StatusRow {
     RepoSummarize {
         name: String,
         branch: String,
+        state: String,
         ahead_behind: String,
         unadded: String,
         deleted: String,
@@ -200,10 +341,15 @@ enum StatusRow {
         status: String,
         path: String,
     },
+    Info {
+        key: String,
+        value: String,
+    },
     SummarizeAll {
         total: String,
         unpushed_repo_count: String,
         uncommited_repo_count: String,
+        in_progress_repo_count: String,
         total_unadded: String,
         total_deleted: String,
         total_modified: String,
@@ -221,21 +367,24 @@ impl StatusRow {
             StatusRow::RepoSeperation => row!["--------------"],
             StatusRow::TitleSeperation => row!["================"],
             StatusRow::FileDetail { status, path } => row![r => status, path],
+            StatusRow::Info { key, value } => row![r => key, value],
             StatusRow::SummarizeAll {
                 total,
                 unpushed_repo_count,
                 uncommited_repo_count,
+                in_progress_repo_count,
                 total_unadded,
                 total_deleted,
                 total_modified,
                 total_conflicted,
                 total_added,
             } => {
-                row![total, uncommited_repo_count, r -> unpushed_repo_count, r -> total_unadded, r -> total_deleted, r -> total_modified, r -> total_conflicted, r -> total_added]
+                row![total, uncommited_repo_count, in_progress_repo_count, r -> unpushed_repo_count, r -> total_unadded, r -> total_deleted, r -> total_modified, r -> total_conflicted, r -> total_added]
             }
             StatusRow::RepoSummarize {
                 name,
                 branch,
+                state,
                 ahead_behind,
                 unadded,
                 deleted,
@@ -243,10 +392,10 @@ impl StatusRow {
                 conflicted,
                 added,
             } => {
-                row![name, branch, r -> ahead_behind, r -> unadded, r -> deleted, r -> modified, r -> conflicted, r -> added]
+                row![name, branch, state, r -> ahead_behind, r -> unadded, r -> deleted, r -> modified, r -> conflicted, r -> added]
             }
             StatusRow::SummarizeTitle => {
-                row!["Repo Count", "Dirty", "fetch/push", r -> "U", r -> "D", r -> "M", r -> "C", r -> "A"]
+                row!["Repo Count", "Dirty", "In progress", "fetch/push", r -> "U", r -> "D", r -> "M", r -> "C", r -> "A"]
             }
         }
     }
@@ -0,0 +1,385 @@
+use super::common;
+use crate::convert::try_from_one;
+use crate::filter::Filter;
+use crate::git;
+use crate::git::Clonable;
+use crate::github;
+use crate::github::{RemoteRepo, Unsuccessful};
+use crate::path;
+use crate::path::EnsureDirExists;
+use crate::user::User;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Reconcile local repositories in root directory with the remote organisation
+///
+/// Repositories that are missing locally will be cloned.
+///
+/// Local directories that don't exist in the organisation anymore are reported as orphaned,
+/// use --attic to move them to <root>/.attic/<organisation>.
+///
+/// Local repositories whose origin points to another organisation or name are reported,
+/// use --fix-remote to update their origin url.
+pub struct SyncArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long)]
+    /// Option to move orphaned directories to the attic directory
+    pub attic: bool,
+    #[structopt(long)]
+    /// Option to fix origin urls that point to another organisation or name
+    pub fix_remote: bool,
+    #[structopt(long)]
+    /// Only report differences, don't clone, move or fix anything
+    pub dry_run: bool,
+    #[structopt(long, short)]
+    /// Option to use https instead of ssh when clone repositories or fix origin urls
+    pub use_https: bool,
+}
+
+impl SyncArgs {
+    pub fn run(&self) -> Result<()> {
+        let user = common::user()?;
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let remote_repos =
+            common::query_and_filter_repositories(&organisation, self.regex.as_ref(), &user.token)?;
+
+        Path::new(&root).join(&organisation).ensure_dir_exists()?;
+        let local_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        let remote_map: HashMap<String, &RemoteRepo> = remote_repos
+            .iter()
+            .map(|r| (r.name.to_lowercase(), r))
+            .collect();
+
+        let local_names: Vec<String> = local_dirs
+            .iter()
+            .filter_map(|d| path::dir_name(d).ok())
+            .map(|n| n.to_lowercase())
+            .collect();
+
+        let missing: Vec<_> = remote_repos
+            .iter()
+            .filter(|r| !local_names.contains(&r.name.to_lowercase()))
+            .collect();
+
+        let mut statuses: Vec<_> = missing
+            .par_iter()
+            .map(|r| self.sync_missing(r, &user))
+            .collect();
+
+        let mut local_statuses: Vec<_> = local_dirs
+            .par_iter()
+            .map(|d| self.sync_local(d, &remote_map, &organisation, &root, &user))
+            .collect();
+
+        statuses.append(&mut local_statuses);
+        statuses.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+        summarize(&statuses, self.dry_run);
+
+        Ok(())
+    }
+
+    fn sync_missing(&self, repo: &RemoteRepo, user: &User) -> Status {
+        let sync = || -> Result<Action> {
+            if self.dry_run {
+                return Ok(Action::Nothing);
+            }
+            let git_repo = try_from_one(repo.clone(), user, self.use_https)?;
+            git_repo.gclone()?;
+            Ok(Action::Cloned)
+        };
+
+        Status {
+            repo: repo.name.clone(),
+            state: State::Missing,
+            action: sync(),
+        }
+    }
+
+    fn sync_local(
+        &self,
+        dir: &PathBuf,
+        remote_map: &HashMap<String, &RemoteRepo>,
+        org: &str,
+        root: &str,
+        user: &User,
+    ) -> Status {
+        let name = match path::dir_name(dir) {
+            Ok(n) => n,
+            Err(e) => {
+                return Status {
+                    repo: format!("{:?}", dir),
+                    state: State::Unknown,
+                    action: Err(e),
+                }
+            }
+        };
+
+        match remote_map.get(&name.to_lowercase()) {
+            Some(remote_repo) => self.sync_origin(dir, &name, remote_repo, user),
+            None => self.sync_orphan(dir, &name, org, root, &user.token),
+        }
+    }
+
+    fn sync_origin(
+        &self,
+        dir: &PathBuf,
+        name: &str,
+        remote_repo: &RemoteRepo,
+        user: &User,
+    ) -> Status {
+        let mut state = State::Ok;
+
+        let mut sync = || -> Result<Action> {
+            let git_repo =
+                git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+            let current = git::remote_url(&git_repo, "origin")?;
+            let is_matched = current
+                .as_deref()
+                .and_then(git::parse_owner_and_name)
+                .map(|(owner, name)| {
+                    owner.eq_ignore_ascii_case(&remote_repo.owner)
+                        && name.eq_ignore_ascii_case(&remote_repo.name)
+                })
+                .unwrap_or(false);
+
+            if is_matched {
+                return Ok(Action::Nothing);
+            }
+
+            state = State::WrongOrigin(current.unwrap_or_else(|| "-".to_string()));
+
+            if self.dry_run || !self.fix_remote {
+                return Ok(Action::Nothing);
+            }
+
+            let expected = try_from_one(remote_repo.clone(), user, self.use_https)?;
+            git::set_remote_url(&git_repo, "origin", &expected.remote_url)?;
+            Ok(Action::FixedOrigin(expected.remote_url))
+        };
+
+        let action = sync();
+
+        Status {
+            repo: name.to_string(),
+            state,
+            action,
+        }
+    }
+
+    fn sync_orphan(&self, dir: &PathBuf, name: &str, org: &str, root: &str, token: &str) -> Status {
+        let state = State::Orphaned(upstream(dir, org, name, token));
+
+        let move_to_attic = || -> Result<Action> {
+            if self.dry_run || !self.attic {
+                return Ok(Action::Nothing);
+            }
+
+            let attic_dir = path::local_path_attic(org, root).ensure_dir_exists()?;
+            let target = attic_dir.join(name);
+            if target.exists() {
+                return Err(anyhow!("{:?} is already exist", target));
+            }
+            std::fs::rename(dir, &target)
+                .with_context(|| format!("Cannot move {:?} to {:?}", dir, target))?;
+            Ok(Action::MovedToAttic(target))
+        };
+
+        Status {
+            repo: name.to_string(),
+            state,
+            action: move_to_attic(),
+        }
+    }
+}
+
+/// Find out what happened to an orphaned repository on github
+///
+/// Github redirects renamed or transferred repositories, so we look up the origin first
+fn upstream(dir: &PathBuf, org: &str, name: &str, token: &str) -> Upstream {
+    let (owner, repo_name) = git::open(dir)
+        .ok()
+        .and_then(|r| git::remote_url(&r, "origin").ok().flatten())
+        .and_then(|url| git::parse_owner_and_name(&url))
+        .unwrap_or_else(|| (org.to_string(), name.to_string()));
+
+    match github::get_repo(&owner, &repo_name, token) {
+        Ok(repo) => {
+            // GitHub answers with the current name, a match means the repo is still in the
+            // organisation under the name origin uses
+            if repo
+                .full_name
+                .eq_ignore_ascii_case(&format!("{}/{}", org, repo_name))
+            {
+                Upstream::Unknown
+            } else {
+                Upstream::Moved(repo.full_name)
+            }
+        }
+        Err(e) => match e.downcast_ref::<Unsuccessful>() {
+            Some(Unsuccessful(StatusCode::NOT_FOUND)) => Upstream::Deleted,
+            _ => Upstream::Unknown,
+        },
+    }
+}
+
+struct Status {
+    repo: String,
+    state: State,
+    action: Result<Action, Error>,
+}
+
+enum State {
+    Ok,
+    Missing,
+    Orphaned(Upstream),
+    WrongOrigin(String),
+    Unknown,
+}
+
+enum Upstream {
+    Deleted,
+    Moved(String),
+    Unknown,
+}
+
+enum Action {
+    Nothing,
+    Cloned,
+    MovedToAttic(PathBuf),
+    FixedOrigin(String),
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            self.state.to_cell(),
+            self.action_to_cell(),
+        ])
+    }
+
+    fn action_to_cell(&self) -> Cell {
+        match &self.action {
+            Ok(Action::Nothing) => cell!(r -> "-"),
+            Ok(Action::Cloned) => cell!(Fgr -> "Cloned"),
+            Ok(Action::MovedToAttic(p)) => cell!(Fgr -> format!("Moved to {}", p.display())),
+            Ok(Action::FixedOrigin(url)) => cell!(Fgr -> format!("Origin set to {}", url)),
+            Err(_) => cell!(Frr -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.action.is_err()
+    }
+
+    fn is_changed(&self) -> bool {
+        !matches!(self.action, Ok(Action::Nothing) | Err(_))
+    }
+
+    fn need_attention(&self) -> bool {
+        matches!(self.action, Ok(Action::Nothing)) && !matches!(self.state, State::Ok)
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.action {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+impl State {
+    fn to_cell(&self) -> Cell {
+        match self {
+            State::Ok => cell!(Fgl -> "Ok"),
+            State::Missing => cell!(Fyl -> "Missing locally"),
+            State::Orphaned(Upstream::Deleted) => cell!(Fyl -> "Orphaned (deleted upstream)"),
+            State::Orphaned(Upstream::Moved(new_name)) => {
+                cell!(Fyl -> format!("Orphaned (moved to {})", new_name))
+            }
+            State::Orphaned(Upstream::Unknown) => cell!(Fyl -> "Orphaned"),
+            State::WrongOrigin(url) => cell!(Fyl -> format!("Origin is {}", url)),
+            State::Unknown => cell!(Frl -> "Unknown"),
+        }
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "State", "Action"]);
+    table
+}
+
+fn summarize(statuses: &[Status], dry_run: bool) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let changes: Vec<_> = statuses.iter().filter(|s| s.is_changed()).collect();
+    let attentions: Vec<_> = statuses.iter().filter(|s| s.need_attention()).collect();
+
+    if !changes.is_empty() {
+        let msg = format!("\nSynced {} repos successfully!", changes.len());
+        println!("{}", msg.green());
+    }
+
+    if !attentions.is_empty() {
+        let msg = if dry_run {
+            format!(
+                "\nThere are {} repos out of sync (dry run)",
+                attentions.len()
+            )
+        } else {
+            format!(
+                "\nThere are {} repos out of sync. Use --attic or --fix-remote to fix them",
+                attentions.len()
+            )
+        };
+        println!("{}", msg.yellow());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
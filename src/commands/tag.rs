@@ -0,0 +1,30 @@
+use super::tag_create::*;
+use super::tag_delete::*;
+use super::tag_list::*;
+use super::tag_push::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create, list, delete or push tags of all local repositories that match a pattern
+pub enum TagArgs {
+    #[structopt(name = "create")]
+    Create(TagCreateArgs),
+    #[structopt(name = "delete")]
+    Delete(TagDeleteArgs),
+    #[structopt(name = "list")]
+    List(TagListArgs),
+    #[structopt(name = "push")]
+    Push(TagPushArgs),
+}
+
+impl TagArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            TagArgs::Create(args) => args.run(),
+            TagArgs::Delete(args) => args.run(),
+            TagArgs::List(args) => args.run(),
+            TagArgs::Push(args) => args.run(),
+        }
+    }
+}
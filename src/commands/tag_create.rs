@@ -0,0 +1,201 @@
+use super::common;
+use super::models::Script;
+use crate::filter::Filter;
+use crate::git;
+use crate::git::GitCredential;
+use crate::path;
+use crate::user::User;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Create a tag at HEAD of all local repositories that match a pattern
+///
+/// The tag is annotated if a message or a message script is provided, otherwise it is a
+/// lightweight tag
+pub struct TagCreateArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Tag name
+    pub tag: String,
+    #[structopt(long, short)]
+    /// Message of an annotated tag
+    pub message: Option<String>,
+    #[structopt(long)]
+    /// The script that will produce the message for each repository
+    ///
+    /// It will override --message
+    pub message_script: Option<Script>,
+    #[structopt(long, short)]
+    /// Option to replace the tag if it already exists
+    pub force: bool,
+    #[structopt(long, short)]
+    /// Option to push the tag to origin after creating
+    pub push: bool,
+}
+
+impl TagCreateArgs {
+    pub fn run(&self) -> Result<()> {
+        let user = common::user()?;
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = sub_dirs
+            .par_iter()
+            .map(|d| self.create(d, &organisation, &user))
+            .collect();
+
+        summarize(&statuses, &self.tag);
+
+        Ok(())
+    }
+
+    fn create(&self, dir: &PathBuf, organisation: &str, user: &User) -> Status {
+        let mut dir_name = "".to_string();
+        let mut sha = "".to_string();
+        let mut pushed = false;
+
+        let mut create = || -> Result<()> {
+            dir_name = path::dir_name(dir)?;
+            log::info!("Processing repo {}", dir_name);
+
+            let git_repo =
+                git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+            let message = match &self.message_script {
+                Some(script) => Some(script.execute_and_get_output(&dir_name, organisation)?),
+                None => self.message.clone(),
+            };
+
+            git::create_tag(&git_repo, &self.tag, message.as_deref(), self.force)?;
+            sha = git::head_sha(&git_repo)?;
+
+            if self.push {
+                let force = if self.force { "+" } else { "" };
+                let refspec = format!("{}refs/tags/{}:refs/tags/{}", force, self.tag, self.tag);
+                let mut remote = git_repo.find_remote("origin")?;
+                let cred = GitCredential::from(user);
+                let updates = git::push_refs(&mut remote, &[refspec], Some(cred))?;
+                if let Some(msg) = updates.iter().find_map(|u| u.rejection.as_ref()) {
+                    return Err(anyhow!("Remote rejected the tag: {}", msg));
+                }
+                pushed = true;
+            }
+
+            Ok(())
+        };
+
+        let result = create();
+
+        Status {
+            repo: dir_name,
+            sha,
+            pushed,
+            result,
+        }
+    }
+}
+
+struct Status {
+    repo: String,
+    sha: String,
+    pushed: bool,
+    result: Result<(), Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        let short_sha = self.sha.get(..8).unwrap_or(&self.sha);
+        let pushed = if self.pushed { "Pushed" } else { "-" };
+        Row::new(vec![
+            cell!(b -> &self.repo),
+            self.result_to_cell(),
+            cell!(short_sha),
+            cell!(r -> pushed),
+        ])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(_) => cell!(Fgl -> "Created"),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.result {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Tag", "Commit", "Push"]);
+    table
+}
+
+fn summarize(statuses: &[Status], tag: &str) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!("\nCreated tag {} for {} repos!", tag, successes.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
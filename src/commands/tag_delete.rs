@@ -0,0 +1,210 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::git::GitCredential;
+use crate::path;
+use crate::user::User;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Delete a tag of all local repositories that match a pattern
+///
+/// With --remote the tag will be deleted on origin as well, it'll show a confirmation prompt and
+/// you have to enter 'YES' to confirm your action
+pub struct TagDeleteArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Tag name
+    pub tag: String,
+    #[structopt(long)]
+    /// Option to delete the tag on origin as well
+    pub remote: bool,
+}
+
+impl TagDeleteArgs {
+    pub fn run(&self) -> Result<()> {
+        let user = common::user()?;
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        if self.remote && !confirm(&self.tag, sub_dirs.len())? {
+            println!("Command is aborted. Nothing got deleted!");
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = sub_dirs.par_iter().map(|d| self.delete(d, &user)).collect();
+
+        summarize(&statuses, &self.tag);
+
+        Ok(())
+    }
+
+    fn delete(&self, dir: &PathBuf, user: &User) -> Status {
+        let mut dir_name = "".to_string();
+        let mut local = Deletion::NotFound;
+
+        let mut delete = || -> Result<Deletion> {
+            dir_name = path::dir_name(dir)?;
+            log::info!("Processing repo {}", dir_name);
+
+            let git_repo =
+                git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+            if git::tag_names(&git_repo)?.contains(&self.tag) {
+                git::delete_tag(&git_repo, &self.tag)?;
+                local = Deletion::Deleted;
+            }
+
+            if !self.remote {
+                return Ok(Deletion::Skipped);
+            }
+
+            let refname = format!("refs/tags/{}", self.tag);
+            let cred = GitCredential::from(user);
+            let mut remote = git_repo.find_remote("origin")?;
+            if !git::remote_refs(&mut remote, Some(cred.clone()))?.contains_key(&refname) {
+                return Ok(Deletion::NotFound);
+            }
+
+            let updates = git::push_refs(&mut remote, &[format!(":{}", refname)], Some(cred))?;
+            if let Some(msg) = updates.iter().find_map(|u| u.rejection.as_ref()) {
+                return Err(anyhow!("Remote rejected the deletion: {}", msg));
+            }
+
+            Ok(Deletion::Deleted)
+        };
+
+        let remote = delete();
+
+        Status {
+            repo: dir_name,
+            local,
+            remote,
+        }
+    }
+}
+
+fn confirm(tag: &str, count: usize) -> Result<bool> {
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to delete tag {} of {} repo(s) on origin?\nEnter {} to continue",
+            tag, count, key
+        ),
+        key,
+    )
+}
+
+enum Deletion {
+    Deleted,
+    NotFound,
+    Skipped,
+}
+
+impl Deletion {
+    fn to_cell(&self) -> Cell {
+        match self {
+            Deletion::Deleted => cell!(Fgl -> "Deleted"),
+            Deletion::NotFound => cell!(l -> "Not found"),
+            Deletion::Skipped => cell!(l -> "-"),
+        }
+    }
+}
+
+struct Status {
+    repo: String,
+    local: Deletion,
+    remote: Result<Deletion, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        let remote = match &self.remote {
+            Ok(d) => d.to_cell(),
+            Err(_) => cell!(Frl -> "Failed"),
+        };
+        Row::new(vec![cell!(b -> &self.repo), self.local.to_cell(), remote])
+    }
+
+    fn is_deleted(&self) -> bool {
+        matches!(self.local, Deletion::Deleted) || matches!(self.remote, Ok(Deletion::Deleted))
+    }
+
+    fn has_error(&self) -> bool {
+        self.remote.is_err()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let e = if let Err(e) = &self.remote {
+            e
+        } else {
+            panic!("This should have an error here");
+        };
+
+        let msg = format!("{:?}", e);
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Local", "Remote"]);
+    table
+}
+
+fn summarize(statuses: &[Status], tag: &str) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let deleted: Vec<_> = statuses.iter().filter(|s| s.is_deleted()).collect();
+
+    if !deleted.is_empty() {
+        let msg = format!("\nDeleted tag {} in {} repos!", tag, deleted.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -0,0 +1,99 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::path;
+use anyhow::{Context, Result};
+use prettytable::{cell, format, row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Show the latest tag reachable from HEAD of all local repositories that match a pattern
+///
+/// It also shows the number of commits since that tag
+pub struct TagListArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Option to also show all tags of each repository
+    pub all: bool,
+}
+
+impl TagListArgs {
+    pub fn run(&self) -> Result<()> {
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let results: Vec<_> = sub_dirs.par_iter().map(list).collect();
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        if self.all {
+            table.set_titles(row!["Repo", "Latest tag", r -> "Commits since", "All tags"]);
+        } else {
+            table.set_titles(row!["Repo", "Latest tag", r -> "Commits since"]);
+        }
+
+        for (name, result) in &results {
+            match result {
+                Ok(tags) => {
+                    let (latest, since) = match &tags.latest {
+                        Some((tag, count)) => (tag.to_string(), count.to_string()),
+                        None => ("-".to_string(), "-".to_string()),
+                    };
+                    if self.all {
+                        table.add_row(row![b -> name, latest, r -> since, tags.all.join("\n")]);
+                    } else {
+                        table.add_row(row![b -> name, latest, r -> since]);
+                    }
+                }
+                Err(e) => println!("Failed to list tags of repo {} because {:?}", name, e),
+            }
+        }
+
+        table.printstd();
+
+        Ok(())
+    }
+}
+
+struct Tags {
+    latest: Option<(String, usize)>,
+    all: Vec<String>,
+}
+
+fn list(dir: &PathBuf) -> (String, Result<Tags>) {
+    let mut dir_name = "".to_string();
+
+    let mut list = || -> Result<Tags> {
+        dir_name = path::dir_name(dir)?;
+
+        let git_repo =
+            git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+        Ok(Tags {
+            latest: git::latest_tag(&git_repo)?,
+            all: git::tag_names(&git_repo)?,
+        })
+    };
+
+    let result = list();
+    (dir_name, result)
+}
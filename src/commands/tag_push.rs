@@ -0,0 +1,198 @@
+use super::common;
+use crate::filter::Filter;
+use crate::git;
+use crate::git::{GitCredential, RefUpdate};
+use crate::path;
+use crate::user::User;
+use anyhow::{Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Push a tag, or all tags, of all local repositories that match a pattern to origin
+pub struct TagPushArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short)]
+    /// Optional regex to filter repositories
+    pub regex: Option<Filter>,
+    #[structopt(long, short)]
+    /// Tag name, all tags will be pushed if it is not provided
+    pub tag: Option<String>,
+}
+
+impl TagPushArgs {
+    pub fn run(&self) -> Result<()> {
+        let user = common::user()?;
+        let root = common::root()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let sub_dirs = common::read_dirs_for_org(&organisation, &root, self.regex.as_ref())?;
+
+        if sub_dirs.is_empty() {
+            println!(
+                "There is no local repositories in organisation {} matches pattern {:?}",
+                organisation, self.regex
+            );
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = sub_dirs
+            .par_iter()
+            .map(|d| push(d, self.tag.as_deref(), &user))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+fn push(dir: &PathBuf, tag: Option<&str>, user: &User) -> Status {
+    let mut dir_name = "".to_string();
+
+    let mut push = || -> Result<Vec<RefUpdate>> {
+        dir_name = path::dir_name(dir)?;
+        log::info!("Processing repo {}", dir_name);
+
+        let git_repo =
+            git::open(dir).with_context(|| format!("{:?} is not a git directory.", dir))?;
+
+        let tags = match tag {
+            Some(tag) => vec![tag.to_string()],
+            None => git::tag_names(&git_repo)?,
+        };
+        let refspecs: Vec<_> = tags
+            .iter()
+            .filter(|t| git_repo.find_reference(&format!("refs/tags/{}", t)).is_ok())
+            .map(|t| format!("refs/tags/{}:refs/tags/{}", t, t))
+            .collect();
+
+        if refspecs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let cred = GitCredential::from(user);
+        let mut remote = git_repo.find_remote("origin")?;
+        let updates = git::push_refs(&mut remote, &refspecs, Some(cred))?;
+        Ok(updates)
+    };
+
+    let result = push();
+
+    Status {
+        repo: dir_name,
+        result,
+    }
+}
+
+struct Status {
+    repo: String,
+    result: Result<Vec<RefUpdate>, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.repo), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(updates) if updates.is_empty() => cell!(l -> "-"),
+            Ok(updates) => {
+                let lines: Vec<_> = updates
+                    .iter()
+                    .map(|u| match &u.rejection {
+                        Some(_) => format!("{} rejected", u.shorthand()),
+                        None => format!("{} ok", u.shorthand()),
+                    })
+                    .collect();
+                if self.is_rejected() {
+                    cell!(Frl -> lines.join("\n"))
+                } else {
+                    cell!(Fgl -> lines.join("\n"))
+                }
+            }
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn is_rejected(&self) -> bool {
+        match &self.result {
+            Ok(updates) => updates.iter().any(|u| u.is_rejected()),
+            Err(_) => false,
+        }
+    }
+
+    fn is_pushed(&self) -> bool {
+        matches!(&self.result, Ok(updates) if !updates.is_empty()) && !self.is_rejected()
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err() || self.is_rejected()
+    }
+
+    fn to_error_row(&self) -> Row {
+        let msg = match &self.result {
+            Err(e) => format!("{:?}", e),
+            Ok(updates) => updates
+                .iter()
+                .filter_map(|u| {
+                    u.rejection
+                        .as_ref()
+                        .map(|msg| format!("{} is rejected: {}", u.refname, msg))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        let lines = common::sub_strings(msg.as_str(), 80);
+        let lines = lines.join("\n");
+        row!(cell!(b -> &self.repo), cell!(Fr -> lines.as_str()))
+    }
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Repo", "Tags"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let pushed: Vec<_> = statuses.iter().filter(|s| s.is_pushed()).collect();
+
+    if !pushed.is_empty() {
+        let msg = format!("\nPushed tags of {} repos!", pushed.len());
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Repo", "Error"]);
+        for error in errors {
+            error_table.add_row(error.to_error_row());
+        }
+        error_table.printstd();
+    }
+}
@@ -0,0 +1,18 @@
+use super::team_sync::*;
+use anyhow::Result;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Manage members of teams
+pub enum TeamArgs {
+    #[structopt(name = "sync")]
+    Sync(TeamSyncArgs),
+}
+
+impl TeamArgs {
+    pub fn run(&self) -> Result<()> {
+        match self {
+            TeamArgs::Sync(args) => args.run(),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::github;
+use anyhow::Result;
+
+/// Current members of a team by role, without the members of its child teams
+pub struct TeamMembership {
+    pub members: Vec<String>,
+    pub maintainers: Vec<String>,
+}
+
+impl TeamMembership {
+    pub fn fetch(org: &str, slug: &str, token: &str) -> Result<TeamMembership> {
+        let (maintainers, members) = github::get_immediate_team_members(org, slug, token)?
+            .into_iter()
+            .partition::<Vec<_>, _>(|m| m.role == "maintainer");
+
+        Ok(TeamMembership {
+            members: members.into_iter().map(|m| m.login).collect(),
+            maintainers: maintainers.into_iter().map(|m| m.login).collect(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MemberChange {
+    Add {
+        user: String,
+        role: String,
+    },
+    /// Change the role of an existing member
+    SetRole {
+        user: String,
+        role: String,
+    },
+    Remove(String),
+}
+
+impl MemberChange {
+    pub fn describe(&self) -> String {
+        match self {
+            MemberChange::Add { user, role } => format!("+ {} ({})", user, role),
+            MemberChange::SetRole { user, role } => format!("~ {} -> {}", user, role),
+            MemberChange::Remove(user) => format!("- {}", user),
+        }
+    }
+}
+
+/// Changes to make the current members of a team match the given members and maintainers
+///
+/// Logins are compared case insensitively. A user listed both as member and maintainer becomes
+/// a maintainer.
+pub fn plan_members(
+    current: &TeamMembership,
+    members: &[String],
+    maintainers: &[String],
+) -> Vec<MemberChange> {
+    let contains =
+        |list: &[String], user: &str| list.iter().any(|u| u.to_lowercase() == user.to_lowercase());
+
+    let desired_maintainers = maintainers.iter().map(|u| (u, "maintainer"));
+    let desired_members = members
+        .iter()
+        .filter(|u| !contains(maintainers, u))
+        .map(|u| (u, "member"));
+
+    let mut changes = vec![];
+
+    for (user, role) in desired_maintainers.chain(desired_members) {
+        let (same_role, other_role) = if role == "maintainer" {
+            (&current.maintainers, &current.members)
+        } else {
+            (&current.members, &current.maintainers)
+        };
+
+        if contains(same_role, user) {
+            continue;
+        }
+
+        let change = if contains(other_role, user) {
+            MemberChange::SetRole {
+                user: user.clone(),
+                role: role.to_string(),
+            }
+        } else {
+            MemberChange::Add {
+                user: user.clone(),
+                role: role.to_string(),
+            }
+        };
+        changes.push(change);
+    }
+
+    for user in current.maintainers.iter().chain(&current.members) {
+        if !contains(maintainers, user) && !contains(members, user) {
+            changes.push(MemberChange::Remove(user.clone()));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_members() {
+        let current = TeamMembership {
+            members: vec![
+                "anna".to_string(),
+                "bo".to_string(),
+                "dag".to_string(),
+                "cecilie".to_string(),
+            ],
+            maintainers: vec!["frida".to_string()],
+        };
+        let members = vec!["anna".to_string(), "bo".to_string(), "Dag".to_string()];
+        let maintainers = vec!["bo".to_string(), "erik".to_string()];
+
+        assert_eq!(
+            plan_members(&current, &members, &maintainers),
+            vec![
+                MemberChange::SetRole {
+                    user: "bo".to_string(),
+                    role: "maintainer".to_string()
+                },
+                MemberChange::Add {
+                    user: "erik".to_string(),
+                    role: "maintainer".to_string()
+                },
+                MemberChange::Remove("frida".to_string()),
+                MemberChange::Remove("cecilie".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use super::common;
+use super::models::{TeamFile, TeamMembers};
+use super::team_helper::{plan_members, MemberChange, TeamMembership};
+use crate::github;
+use crate::github::Team;
+use anyhow::{anyhow, Context, Error, Result};
+use colored::*;
+use prettytable::{cell, format, row, Cell, Row, Table};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+/// Make members and maintainers of teams match a team file
+///
+/// The team file is a toml file with a list of teams:
+///
+/// [[teams]]
+/// name = "linguists"
+/// members = ["anna", "bo"]
+/// maintainers = ["cecilie"]
+///
+/// Members that are not in the file are removed from the team. Teams that are not in the file
+/// are left as they are. The changes are shown first and only applied after a confirmation.
+pub struct TeamSyncArgs {
+    #[structopt(long, short)]
+    /// Target organisation name
+    ///
+    /// You can set a default organisation in the init or set organisation command.
+    pub organisation: Option<String>,
+    #[structopt(long, short, parse(from_os_str))]
+    /// Path to the team file
+    pub file: PathBuf,
+}
+
+impl TeamSyncArgs {
+    pub fn run(&self) -> Result<()> {
+        let user_token = common::user_token()?;
+        let organisation = common::organisation(self.organisation.as_deref())?;
+
+        let team_file = TeamFile::get(&self.file)
+            .with_context(|| format!("Cannot read team file {:?}", self.file))?;
+
+        let teams = github::get_teams(&organisation, &user_token)?;
+
+        let plans: Vec<_> = team_file
+            .teams
+            .par_iter()
+            .map(|t| {
+                let team = teams.iter().find(|g| g.name == t.name || g.slug == t.name);
+                Plan {
+                    team: t.name.clone(),
+                    slug: team.map(|g| g.slug.clone()).unwrap_or_default(),
+                    result: fetch_and_plan(&organisation, team, t, &user_token),
+                }
+            })
+            .collect();
+
+        for plan in &plans {
+            plan.print();
+        }
+
+        let changes: Vec<_> = plans.iter().filter(|p| p.change_count() > 0).collect();
+        let errors: Vec<_> = plans.iter().filter(|p| p.result.is_err()).collect();
+
+        if !errors.is_empty() {
+            let msg = format!(
+                "There are {} teams that will be skipped because of errors:",
+                errors.len()
+            );
+            println!("\n{}\n", msg.red());
+
+            let mut error_table = Table::new();
+            error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+            error_table.set_titles(row!["Team", "Error"]);
+            for error in errors {
+                if let Err(e) = &error.result {
+                    error_table.add_row(to_error_row(&error.team, e));
+                }
+            }
+            error_table.printstd();
+        }
+
+        if changes.is_empty() {
+            println!("\nMembers of all teams are up to date!");
+            return Ok(());
+        }
+
+        let change_count: usize = changes.iter().map(|p| p.change_count()).sum();
+        if !confirm(change_count, changes.len())? {
+            println!("Command is aborted. Nothing is changed!");
+            return Ok(());
+        }
+
+        let statuses: Vec<_> = changes
+            .par_iter()
+            .map(|p| apply(p, &organisation, &user_token))
+            .collect();
+
+        summarize(&statuses);
+
+        Ok(())
+    }
+}
+
+fn fetch_and_plan(
+    org: &str,
+    team: Option<&Team>,
+    members: &TeamMembers,
+    token: &str,
+) -> Result<Vec<MemberChange>> {
+    let team = team.ok_or_else(|| anyhow!("Team {} doesn't exist", members.name))?;
+
+    let current = TeamMembership::fetch(org, &team.slug, token)?;
+
+    Ok(plan_members(
+        &current,
+        &members.members,
+        &members.maintainers,
+    ))
+}
+
+fn apply(plan: &Plan, org: &str, token: &str) -> Status {
+    log::info!("Processing team {}", plan.team);
+
+    let apply = || -> Result<usize> {
+        let changes = match &plan.result {
+            Ok(changes) => changes,
+            Err(_) => return Ok(0),
+        };
+
+        for change in changes {
+            match change {
+                MemberChange::Add { user, role } | MemberChange::SetRole { user, role } => {
+                    github::add_user_to_team(org, &plan.slug, role, user, token)
+                }
+                MemberChange::Remove(user) => {
+                    github::remove_user_from_team(org, &plan.slug, user, token)
+                }
+            }
+            .with_context(|| format!("Failed to apply {}", change.describe()))?;
+        }
+
+        Ok(changes.len())
+    };
+
+    Status {
+        team: plan.team.clone(),
+        result: apply(),
+    }
+}
+
+fn confirm(changes: usize, teams: usize) -> Result<bool> {
+    let key = "YES";
+    common::confirm(
+        &format!(
+            "Are you sure you want to make {} member change(s) in {} team(s)?\nEnter {} to continue",
+            changes, teams, key
+        ),
+        key,
+    )
+}
+
+struct Plan {
+    team: String,
+    slug: String,
+    result: Result<Vec<MemberChange>, Error>,
+}
+
+impl Plan {
+    fn print(&self) {
+        let changes = match &self.result {
+            Ok(changes) if !changes.is_empty() => changes,
+            _ => return,
+        };
+
+        println!("{}", self.team.bold());
+        for change in changes {
+            let line = change.describe();
+            match change {
+                MemberChange::Add { .. } => println!("  {}", line.green()),
+                MemberChange::SetRole { .. } => println!("  {}", line.yellow()),
+                MemberChange::Remove(_) => println!("  {}", line.red()),
+            }
+        }
+        println!();
+    }
+
+    fn change_count(&self) -> usize {
+        match &self.result {
+            Ok(changes) => changes.len(),
+            Err(_) => 0,
+        }
+    }
+}
+
+struct Status {
+    team: String,
+    result: Result<usize, Error>,
+}
+
+impl Status {
+    fn to_row(&self) -> Row {
+        Row::new(vec![cell!(b -> &self.team), self.result_to_cell()])
+    }
+
+    fn result_to_cell(&self) -> Cell {
+        match &self.result {
+            Ok(count) => cell!(Fgl -> format!("{} changes", count)),
+            Err(_) => cell!(Frl -> "Failed"),
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        self.result.is_err()
+    }
+}
+
+fn to_error_row(team: &str, e: &Error) -> Row {
+    let msg = format!("{:?}", e);
+    let lines = common::sub_strings(msg.as_str(), 80);
+    let lines = lines.join("\n");
+    row!(cell!(b -> team), cell!(Fr -> lines.as_str()))
+}
+
+fn to_table(statuses: &[Status]) -> Table {
+    let rows: Vec<_> = statuses.par_iter().map(|s| s.to_row()).collect();
+    let mut table = Table::init(rows);
+    table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+    table.set_titles(row!["Team", "Result"]);
+    table
+}
+
+fn summarize(statuses: &[Status]) {
+    let table = to_table(statuses);
+    table.printstd();
+
+    let errors: Vec<_> = statuses.iter().filter(|s| s.has_error()).collect();
+    let successes: Vec<_> = statuses.iter().filter(|s| !s.has_error()).collect();
+
+    if !successes.is_empty() {
+        let msg = format!(
+            "\nSynced members of {} teams successfully!",
+            successes.len()
+        );
+        println!("{}", msg.green());
+    }
+
+    if errors.is_empty() {
+        println!("\nThere is no error!");
+    } else {
+        let msg = format!(
+            "There are {} errors when executing the command:",
+            errors.len()
+        );
+        println!("\n{}\n", msg.red());
+
+        let mut error_table = Table::new();
+        error_table.set_format(*format::consts::FORMAT_BORDERS_ONLY);
+        error_table.set_titles(row!["Team", "Error"]);
+        for error in errors {
+            if let Err(e) = &error.result {
+                error_table.add_row(to_error_row(&error.team, e));
+            }
+        }
+        error_table.printstd();
+    }
+}
@@ -0,0 +1,146 @@
+/// Parse a UTC date in the format YYYY-MM-DD, YYYY-MM-DDTHH:MM or YYYY-MM-DDTHH:MM:SS
+/// to a unix timestamp
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    let (date, time) = match s.find(&['T', ' '][..]) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+
+    let date: Vec<_> = date.split('-').collect();
+    if date.len() != 3 || date[0].len() != 4 {
+        return None;
+    }
+    let year: i64 = date[0].parse().ok()?;
+    let month: i64 = date[1].parse().ok()?;
+    let day: i64 = date[2].parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let (hour, minute, second) = match time {
+        None => (0, 0, 0),
+        Some(time) => {
+            let time: Vec<_> = time.trim_end_matches('Z').split(':').collect();
+            if time.len() < 2 || time.len() > 3 {
+                return None;
+            }
+            let hour: i64 = time[0].parse().ok()?;
+            let minute: i64 = time[1].parse().ok()?;
+            let second: i64 = match time.get(2) {
+                Some(s) => s.parse().ok()?,
+                None => 0,
+            };
+            if hour > 23 || minute > 59 || second > 59 {
+                return None;
+            }
+            (hour, minute, second)
+        }
+    };
+
+    let days = days_from_civil(year, month, day);
+    Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Format a unix timestamp as a UTC date in the format YYYY-MM-DD HH:MM
+pub fn format_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/// Format a unix timestamp as an ISO 8601 UTC time, YYYY-MM-DDTHH:MM:SSZ
+pub fn format_iso_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86400);
+    let seconds = timestamp.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days since 1970-01-01
+///
+/// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Date from the number of days since 1970-01-01
+///
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_timestamp("1970-01-01"), Some(0));
+        assert_eq!(parse_timestamp("2000-03-01"), Some(951868800));
+        assert_eq!(parse_timestamp("2026-05-01"), Some(1777593600));
+        assert_eq!(parse_timestamp("2026-05-01T12:30"), Some(1777638600));
+        assert_eq!(parse_timestamp("2026-05-01T12:30:15Z"), Some(1777638615));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951868800), "2000-03-01 00:00");
+        assert_eq!(format_timestamp(1777638615), "2026-05-01 12:30");
+        assert_eq!(format_iso_timestamp(1777638615), "2026-05-01T12:30:15Z");
+    }
+
+    #[test]
+    fn test_parse_invalid_date() {
+        assert_eq!(parse_timestamp("v1.0.0"), None);
+        assert_eq!(parse_timestamp("2026-13-01"), None);
+        assert_eq!(parse_timestamp("2025-02-29"), None);
+        assert_eq!(parse_timestamp("2026-05-01T25:00"), None);
+        assert_eq!(parse_timestamp("26-05-01"), None);
+    }
+}
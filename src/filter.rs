@@ -1,4 +1,4 @@
-use crate::github::{RemoteRepo, RemoteRepoWithTopics};
+use crate::github::{PullRequestStatus, RemoteRepo, RemoteRepoWithTopics, Team};
 use crate::path;
 use regex::{Error as RegexError, Regex, RegexBuilder};
 use std::path::PathBuf;
@@ -36,6 +36,59 @@ impl fmt::Display for Filter {
     }
 }
 
+/// Shell style path pattern
+///
+/// `*` matches anything except `/`, `**/` matches zero or more directories, a trailing `**`
+/// matches anything and `?` matches one character except `/`. A pattern without `/` is matched
+/// against the file name only.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    regex: Regex,
+    file_name_only: bool,
+}
+
+impl FromStr for Glob {
+    type Err = RegexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pattern = "^".to_string();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        pattern.push_str("(?:.*/)?");
+                    } else {
+                        pattern.push_str(".*");
+                    }
+                }
+                '*' => pattern.push_str("[^/]*"),
+                '?' => pattern.push_str("[^/]"),
+                c => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        pattern.push('$');
+
+        Regex::new(&pattern).map(|regex| Glob {
+            regex,
+            file_name_only: !s.contains('/'),
+        })
+    }
+}
+
+impl Glob {
+    pub fn is_match(&self, path: &str) -> bool {
+        if self.file_name_only {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            self.regex.is_match(name)
+        } else {
+            self.regex.is_match(path)
+        }
+    }
+}
+
 pub trait Filterable {
     fn is_match(&self, filter: &Filter) -> bool;
     fn filter<T: Filterable>(vec: Vec<T>, filter: &Filter) -> Vec<T> {
@@ -61,6 +114,18 @@ impl Filterable for RemoteRepoWithTopics {
     }
 }
 
+impl Filterable for PullRequestStatus {
+    fn is_match(&self, filter: &Filter) -> bool {
+        filter.is_match(&self.repo)
+    }
+}
+
+impl Filterable for Team {
+    fn is_match(&self, filter: &Filter) -> bool {
+        filter.is_match(&self.slug)
+    }
+}
+
 impl Filterable for PathBuf {
     fn is_match(&self, filter: &Filter) -> bool {
         match path::dir_name(self) {
@@ -82,4 +147,31 @@ mod tests {
         assert_eq!(false, filter.is_match("template-lang-sma"));
         assert_eq!(false, filter.is_match("langCI-sma-old"))
     }
+
+    #[test]
+    fn test_glob() {
+        let glob = Glob::from_str("*.mk").unwrap();
+        assert!(glob.is_match("Makefile.mk"));
+        assert!(glob.is_match("build/rules.mk"));
+        assert!(!glob.is_match("rules.mkx"));
+
+        let glob = Glob::from_str("src/**/*.xml").unwrap();
+        assert!(glob.is_match("src/layouts/keyboard.xml"));
+        assert!(glob.is_match("src/x.xml"));
+        assert!(!glob.is_match("test/keyboard.xml"));
+        assert!(!glob.is_match("srcx.xml"));
+
+        let glob = Glob::from_str("**/*.am").unwrap();
+        assert!(glob.is_match("Makefile.am"));
+        assert!(glob.is_match("src/morphology/Makefile.am"));
+        assert!(!glob.is_match("Makefile.in"));
+
+        let glob = Glob::from_str("doc/**").unwrap();
+        assert!(glob.is_match("doc/a/b.md"));
+        assert!(!glob.is_match("src/doc.md"));
+
+        let glob = Glob::from_str("src/*.?s").unwrap();
+        assert!(glob.is_match("src/main.rs"));
+        assert!(!glob.is_match("src/a/main.rs"));
+    }
 }
@@ -1,7 +1,8 @@
 use super::fetch;
 use super::models::GitCredential;
 use anyhow::{anyhow, Result};
-use git2::{Branch, BranchType, Error, Repository};
+use git2::build::CheckoutBuilder;
+use git2::{Branch, BranchType, Error, Oid, Repository};
 
 pub trait CreateBranch<'a> {
     fn create_branch(&self, new_branch: &str, base_branch: &str) -> Result<Branch<'a>, Error>;
@@ -30,12 +31,23 @@ pub fn head_shorthand(repo: &Repository) -> Result<String> {
 
 pub fn checkout_local_branch(repo: &Repository, branch_name: &str) -> Result<()> {
     let obj = repo.revparse_single(&("refs/heads/".to_owned() + branch_name))?;
-    repo.checkout_tree(&obj, None)?;
+    // safe checkout fails instead of overwriting local changes
+    repo.checkout_tree(&obj, Some(CheckoutBuilder::new().safe()))?;
     repo.set_head(&("refs/heads/".to_owned() + branch_name))?;
 
     Ok(())
 }
 
+/// Checkout a commit and detach HEAD at it
+pub fn checkout_detached(repo: &Repository, sha: &str) -> Result<()> {
+    let oid = Oid::from_str(sha)?;
+    let commit = repo.find_commit(oid)?;
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    repo.set_head_detached(oid)?;
+
+    Ok(())
+}
+
 pub fn checkout_remote_branch<'a>(
     repo: &'a Repository,
     branch: &str,
@@ -55,8 +67,31 @@ pub fn checkout_remote_branch<'a>(
         Ok(found_branch) => {
             let oid = found_branch.get().target().unwrap();
             let commit = repo.find_commit(oid)?;
-            repo.branch(&branch, &commit, false)?;
+            let mut local_branch = repo.branch(&branch, &commit, false)?;
+            local_branch.set_upstream(Some(&remote_branch))?;
             checkout_local_branch(repo, branch)
         }
     }
 }
+
+/// Set upstream of a local branch to the remote branch with the same name
+///
+/// Nothing happens if the local branch already has an upstream or the remote branch doesn't
+/// exist. Return true if the upstream is set.
+pub fn track_remote_branch(repo: &Repository, branch: &str, remote_name: &str) -> Result<bool> {
+    let mut local_branch = repo.find_branch(branch, BranchType::Local)?;
+    if local_branch.upstream().is_ok() {
+        return Ok(false);
+    }
+
+    let remote_branch = format!("{}/{}", remote_name, branch);
+    if repo
+        .find_branch(&remote_branch, BranchType::Remote)
+        .is_err()
+    {
+        return Ok(false);
+    }
+
+    local_branch.set_upstream(Some(&remote_branch))?;
+    Ok(true)
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
-use git2::{Diff, DiffDelta, DiffFile, DiffHunk, DiffLine, DiffOptions, Error, Repository};
+use git2::{Diff, DiffDelta, DiffFile, DiffHunk, DiffLine, DiffOptions, Error, Patch, Repository};
+use std::path::Path;
 use std::str;
 
 pub fn diff_trees<'a>(repo: &'a Repository, old: &str, new: &str) -> Result<Diff<'a>, Error> {
@@ -13,6 +14,15 @@ pub fn diff_trees<'a>(repo: &'a Repository, old: &str, new: &str) -> Result<Diff
     repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut opts))
 }
 
+/// Unified diff between two versions of a file
+pub fn diff_contents(path: &str, old: &str, new: &str) -> Result<String, Error> {
+    let path = Path::new(path);
+    let mut patch =
+        Patch::from_buffers(old.as_bytes(), Some(path), new.as_bytes(), Some(path), None)?;
+    let buf = patch.to_buf()?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
 #[allow(dead_code)]
 fn print_stats(diff: &Diff) -> Result<()> {
     let stats = diff.stats()?;
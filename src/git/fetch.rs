@@ -25,6 +25,23 @@ pub fn fetch_branch<'a>(
     Ok(repo.reference_to_annotated_commit(&fetch_head)?)
 }
 
+/// Fetch all branches and tags of a remote without printing progress
+pub fn fetch_all(
+    repo: &Repository,
+    remote_name: &str,
+    cred: Option<GitCredential>,
+) -> Result<(), Error> {
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let remote_callbacks = common::create_remote_callback(&cred)?;
+
+    let mut fo = git2::FetchOptions::new();
+    fo.remote_callbacks(remote_callbacks)
+        .download_tags(AutotagOption::All);
+
+    remote.fetch(&[] as &[&str], Some(&mut fo), None)
+}
+
 pub fn fetch(
     repo: &Repository,
     remote_name: &str,
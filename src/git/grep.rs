@@ -0,0 +1,90 @@
+use git2::{Error, ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use std::fs;
+use walkdir::WalkDir;
+
+/// Call `f` with the path and content of every file in the HEAD tree
+pub fn for_each_head_file<F>(repo: &Repository, mut f: F) -> Result<(), Error>
+where
+    F: FnMut(&str, &[u8]),
+{
+    let tree = repo.head()?.peel_to_tree()?;
+    let mut error = None;
+
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() != Some(ObjectType::Blob) {
+            return TreeWalkResult::Ok;
+        }
+
+        let name = match entry.name() {
+            Some(name) => name,
+            None => return TreeWalkResult::Ok,
+        };
+
+        match repo.find_blob(entry.id()) {
+            Ok(blob) => {
+                if !blob.is_binary() {
+                    f(&format!("{}{}", dir, name), blob.content());
+                }
+                TreeWalkResult::Ok
+            }
+            Err(e) => {
+                error = Some(e);
+                TreeWalkResult::Abort
+            }
+        }
+    })?;
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Call `f` with the path and content of every file in the working tree that is not ignored
+pub fn for_each_worktree_file<F>(repo: &Repository, mut f: F) -> Result<(), Error>
+where
+    F: FnMut(&str, &[u8]),
+{
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| Error::from_str("Repository has no working tree"))?;
+
+    let walker = WalkDir::new(workdir).into_iter().filter_entry(|e| {
+        let relative = match e.path().strip_prefix(workdir) {
+            Ok(relative) => relative,
+            Err(_) => return false,
+        };
+        if relative.as_os_str().is_empty() {
+            return true;
+        }
+        if relative.starts_with(".git") {
+            return false;
+        }
+        !repo.is_path_ignored(relative).unwrap_or(false)
+    });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = match entry
+            .path()
+            .strip_prefix(workdir)
+            .ok()
+            .and_then(|p| p.to_str())
+        {
+            Some(path) => path.to_string(),
+            None => continue,
+        };
+
+        if let Ok(content) = fs::read(entry.path()) {
+            // Same heuristic as git, a file with a NUL byte in the first 8000 bytes is binary
+            if !content.iter().take(8000).any(|b| *b == 0) {
+                f(&path, &content);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use git2::{DiffOptions, Error, ObjectType, Oid, Repository, Sort};
+
+/// Find the last commit on the first-parent history of `from` that was committed before `time`
+pub fn last_commit_before(repo: &Repository, from: Oid, time: i64) -> Result<Option<Oid>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(from)?;
+    revwalk.simplify_first_parent()?;
+    revwalk.set_sorting(Sort::TIME)?;
+
+    for oid in revwalk {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        if commit.time().seconds() < time {
+            return Ok(Some(oid));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Time of a tag, the tagger time for annotated tags or the commit time for lightweight tags
+///
+/// Return None if the tag doesn't exist
+pub fn tag_time(repo: &Repository, tag: &str) -> Result<Option<i64>, Error> {
+    let obj = match repo.revparse_single(&format!("refs/tags/{}", tag)) {
+        Ok(obj) => obj,
+        Err(_) => return Ok(None),
+    };
+
+    if obj.kind() == Some(ObjectType::Tag) {
+        if let Some(tagger) = obj.as_tag().and_then(|t| t.tagger()) {
+            return Ok(Some(tagger.when().seconds()));
+        }
+    }
+
+    let commit = obj.peel_to_commit()?;
+    Ok(Some(commit.time().seconds()))
+}
+
+/// Commits reachable from `to` but not from `from`, newest first
+pub fn commits_between(
+    repo: &Repository,
+    from: Option<Oid>,
+    to: Oid,
+) -> Result<Vec<(Oid, String)>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(to)?;
+    if let Some(from) = from {
+        revwalk.hide(from)?;
+    }
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    let mut commits = vec![];
+    for oid in revwalk {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        let summary = commit.summary().unwrap_or("").to_string();
+        commits.push((oid, summary));
+    }
+
+    Ok(commits)
+}
+
+pub struct LogCommit {
+    pub oid: Oid,
+    pub author: String,
+    pub email: String,
+    pub time: i64,
+    pub message: String,
+}
+
+/// Commits reachable from HEAD committed between `since` and `until`, newest first
+pub fn log(
+    repo: &Repository,
+    since: Option<i64>,
+    until: Option<i64>,
+) -> Result<Vec<LogCommit>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.set_sorting(Sort::TIME)?;
+
+    let mut commits = vec![];
+    for oid in revwalk {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        let time = commit.time().seconds();
+        if matches!(until, Some(until) if time >= until) {
+            continue;
+        }
+        // Commit times are not monotonic after rebases and merges, so an older commit doesn't
+        // mean that the rest of the walk is older too
+        if matches!(since, Some(since) if time < since) {
+            continue;
+        }
+
+        let author = commit.author();
+        commits.push(LogCommit {
+            oid,
+            author: author.name().unwrap_or("").to_string(),
+            email: author.email().unwrap_or("").to_string(),
+            time,
+            message: commit.message().unwrap_or("").to_string(),
+        });
+    }
+
+    Ok(commits)
+}
+
+/// Check if a commit changes any file under `path` compared to its first parent
+pub fn touches_path(repo: &Repository, oid: Oid, path: &str) -> Result<bool, Error> {
+    let commit = repo.find_commit(oid)?;
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+
+    let mut opts = DiffOptions::new();
+    opts.pathspec(path);
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+    Ok(diff.deltas().len() > 0)
+}
@@ -1,5 +1,6 @@
 use super::commit;
-use git2::{AnnotatedCommit, Error, Index, Repository};
+use git2::{AnnotatedCommit, Error, Index, Oid, Repository, RepositoryState, ResetType};
+use std::fs;
 
 #[derive(Debug)]
 pub enum MergeStatus {
@@ -93,7 +94,9 @@ fn normal_merge(
             return Ok(MergeStatus::SkipByConflict);
         }
 
-        repo.checkout_index(Some(&mut idx), None)?;
+        // let libgit2 write MERGE_HEAD so the merge can be continued or aborted later
+        repo.merge(&[remote], None, None)?;
+        let _ = fs::write(repo.path().join("MERGE_MSG"), msg);
         return Ok(MergeStatus::MergeWithConflict);
     }
 
@@ -105,6 +108,53 @@ fn normal_merge(
     Ok(MergeStatus::NormalMerge)
 }
 
+/// Commit an unfinished merge after its conflicts are resolved and staged
+///
+/// Return MergeWithConflict if the index still has conflicts
+pub fn continue_merge(repo: &Repository) -> Result<MergeStatus, Error> {
+    if repo.state() != RepositoryState::Merge {
+        return Ok(MergeStatus::Nothing);
+    }
+
+    let mut idx = repo.index()?;
+    if idx.has_conflicts() {
+        show_conflicts(&idx)?;
+        return Ok(MergeStatus::MergeWithConflict);
+    }
+
+    let tree = repo.find_tree(idx.write_tree()?)?;
+    let head_commit = repo.head()?.peel_to_commit()?;
+    let merge_heads = merge_heads(repo)?;
+
+    let mut parents = vec![&head_commit];
+    parents.extend(merge_heads.iter());
+
+    let msg = repo.message()?;
+    let sig = repo.signature()?;
+    repo.commit(Some("HEAD"), &sig, &sig, &msg, &tree, &parents)?;
+    repo.cleanup_state()?;
+
+    Ok(MergeStatus::NormalMerge)
+}
+
+/// Abort an unfinished merge and go back to HEAD
+pub fn abort_merge(repo: &Repository) -> Result<(), Error> {
+    let head_commit = repo.head()?.peel_to_commit()?;
+    repo.reset(head_commit.as_object(), ResetType::Hard, None)?;
+    repo.cleanup_state()
+}
+
+fn merge_heads<'a>(repo: &'a Repository) -> Result<Vec<git2::Commit<'a>>, Error> {
+    let content = fs::read_to_string(repo.path().join("MERGE_HEAD"))
+        .map_err(|e| Error::from_str(&format!("Cannot read MERGE_HEAD: {}", e)))?;
+
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Oid::from_str(l.trim()).and_then(|oid| repo.find_commit(oid)))
+        .collect()
+}
+
 fn show_conflicts(idx: &Index) -> Result<(), Error> {
     let conflitcs = idx.conflicts()?;
     for c in conflitcs {
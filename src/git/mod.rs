@@ -4,15 +4,19 @@ pub mod commit;
 pub mod common;
 pub mod diff;
 pub mod fetch;
+pub mod grep;
+pub mod history;
 pub mod merge;
 pub mod models;
 pub mod open;
 pub mod pull;
 pub mod push;
 pub mod rebase;
+pub mod remote;
 pub mod sha;
 pub mod stash;
 pub mod status;
+pub mod tag;
 pub mod tree;
 
 pub use branch::*;
@@ -20,13 +24,17 @@ pub use clone::{Clonable, CloneError};
 pub use commit::*;
 pub use diff::*;
 pub use fetch::*;
+pub use grep::*;
+pub use history::*;
 pub use merge::*;
 pub use models::*;
 pub use open::*;
 pub use pull::*;
-pub use push::push_branch;
+pub use push::{push_branch, push_refs, remote_refs, RefUpdate};
 pub use rebase::*;
+pub use remote::*;
 pub use sha::*;
 pub use stash::*;
 pub use status::*;
+pub use tag::*;
 pub use tree::*;
@@ -1,6 +1,28 @@
 use super::common;
 use super::models::GitCredential;
-use git2::{BranchType, Error, Remote, Repository};
+use git2::{BranchType, Direction, Error, Oid, Remote, Repository};
+use std::collections::HashMap;
+
+/// Result of pushing one reference
+#[derive(Debug, Clone)]
+pub struct RefUpdate {
+    pub refname: String,
+    /// Message from the remote if it rejected the update
+    pub rejection: Option<String>,
+}
+
+impl RefUpdate {
+    pub fn is_rejected(&self) -> bool {
+        self.rejection.is_some()
+    }
+
+    /// Short name of the reference, e.g. main for refs/heads/main
+    pub fn shorthand(&self) -> &str {
+        self.refname
+            .trim_start_matches("refs/heads/")
+            .trim_start_matches("refs/tags/")
+    }
+}
 
 pub fn push_branch(
     repo: &Repository,
@@ -9,39 +31,95 @@ pub fn push_branch(
     cred: Option<GitCredential>,
 ) -> Result<(), Error> {
     let mut origin = repo.find_remote(remote_name)?;
+    let updates = push_refs(&mut origin, &[common::ref_by_branch(branch)], cred)?;
+    rejected_to_error(&updates)
+}
 
-    let remote_callbacks = common::create_remote_callback(&cred)?;
+pub fn push(
+    repo: &Repository,
+    remote: &mut Remote,
+    cred: Option<GitCredential>,
+) -> Result<(), Error> {
+    let mut branches = vec![];
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()? {
+            branches.push(name.to_string());
+        }
+    }
 
-    let mut po = git2::PushOptions::new();
-    po.remote_callbacks(remote_callbacks);
+    log::debug!("Branches {:?}", branches);
 
-    origin.push(&[&common::ref_by_branch(branch)], Some(&mut po))?;
+    let refs: Vec<String> = branches.iter().map(|a| common::ref_by_branch(a)).collect();
 
-    Ok(())
+    let updates = push_refs(remote, &refs, cred)?;
+    log::debug!("Push result {:?}", updates);
+    rejected_to_error(&updates)
 }
 
-pub fn push(
-    repo: &Repository,
+/// Push refspecs to a remote and collect the result of every reference
+///
+/// A reference rejected by the remote doesn't make this function fail, check
+/// `RefUpdate::is_rejected` instead
+pub fn push_refs(
     remote: &mut Remote,
+    refspecs: &[String],
     cred: Option<GitCredential>,
-) -> Result<(), Error> {
-    let remote_callbacks = common::create_remote_callback(&cred)?;
+) -> Result<Vec<RefUpdate>, Error> {
+    let mut updates = vec![];
+
+    {
+        let mut remote_callbacks = common::create_remote_callback(&cred)?;
+        remote_callbacks.push_update_reference(|refname, status| {
+            updates.push(RefUpdate {
+                refname: refname.to_string(),
+                rejection: status.map(|s| s.to_string()),
+            });
+            Ok(())
+        });
 
-    let mut po = git2::PushOptions::new();
-    po.remote_callbacks(remote_callbacks);
+        let mut po = git2::PushOptions::new();
+        po.remote_callbacks(remote_callbacks);
 
-    let branches: Vec<String> = repo
-        .branches(Some(BranchType::Local))
-        .unwrap()
-        .map(|a| a.unwrap())
-        .map(|(a, _)| a.name().unwrap().unwrap().to_string())
+        remote.push(refspecs, Some(&mut po))?;
+    }
+
+    Ok(updates)
+}
+
+/// Current target of every reference on a remote
+pub fn remote_refs(
+    remote: &mut Remote,
+    cred: Option<GitCredential>,
+) -> Result<HashMap<String, Oid>, Error> {
+    let remote_callbacks = common::create_remote_callback(&cred)?;
+    let connection = remote.connect_auth(Direction::Push, Some(remote_callbacks), None)?;
+
+    let refs = connection
+        .list()?
+        .iter()
+        .map(|head| (head.name().to_string(), head.oid()))
         .collect();
 
-    log::debug!("Branches {:?}", branches);
+    Ok(refs)
+}
 
-    let refs: Vec<String> = branches.iter().map(|a| common::ref_by_branch(a)).collect();
+fn rejected_to_error(updates: &[RefUpdate]) -> Result<(), Error> {
+    let rejected: Vec<_> = updates
+        .iter()
+        .filter_map(|u| {
+            u.rejection
+                .as_ref()
+                .map(|msg| format!("{} ({})", u.refname, msg))
+        })
+        .collect();
 
-    let result = remote.push(&refs, Some(&mut po));
-    log::debug!("Push result {:?}", result);
-    Ok(())
+    if rejected.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::from_str(&format!(
+            "Remote rejected {}",
+            rejected.join(", ")
+        )))
+    }
 }
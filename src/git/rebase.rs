@@ -1,4 +1,4 @@
-use git2::{AnnotatedCommit, Error, Index, Repository};
+use git2::{AnnotatedCommit, Error, ErrorCode, Index, Rebase, Repository};
 
 #[derive(Debug)]
 pub enum RebaseStatus {
@@ -30,6 +30,44 @@ fn normal_rebase(
     abort_if_conflict: bool,
 ) -> Result<RebaseStatus, git2::Error> {
     let mut operations = repo.rebase(Some(&local), Some(&remote), None, None)?;
+    apply_operations(repo, &mut operations, abort_if_conflict)
+}
+
+/// Continue an unfinished rebase after its conflicts are resolved and staged
+///
+/// Return RebaseWithConflict if there are conflicts again
+pub fn continue_rebase(repo: &Repository) -> Result<RebaseStatus, Error> {
+    let mut operations = repo.open_rebase(None)?;
+
+    let idx = repo.index()?;
+    if idx.has_conflicts() {
+        show_conflicts(&idx)?;
+        return Ok(RebaseStatus::RebaseWithConflict);
+    }
+
+    // commit the operation that stopped by conflicts
+    let sig = repo.signature()?;
+    if operations.operation_current().is_some() {
+        match operations.commit(None, &sig, None) {
+            Err(e) if e.code() != ErrorCode::Applied => return Err(e),
+            _ => {}
+        }
+    }
+
+    apply_operations(repo, &mut operations, false)
+}
+
+/// Abort an unfinished rebase and go back to the original branch
+pub fn abort_rebase(repo: &Repository) -> Result<(), Error> {
+    let mut operations = repo.open_rebase(None)?;
+    operations.abort()
+}
+
+fn apply_operations(
+    repo: &Repository,
+    operations: &mut Rebase,
+    abort_if_conflict: bool,
+) -> Result<RebaseStatus, Error> {
     let sig = repo.signature()?;
     while let Some(operation) = operations.next() {
         let operation = operation?;
@@ -0,0 +1,84 @@
+use git2::{Error, ErrorCode, Repository};
+
+/// Return the url of a remote, or None if the remote doesn't exist
+pub fn remote_url(repo: &Repository, remote_name: &str) -> Result<Option<String>, Error> {
+    match repo.find_remote(remote_name) {
+        Ok(remote) => Ok(remote.url().map(|s| s.to_string())),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Set the url of a remote, the remote will be created if it doesn't exist
+pub fn set_remote_url(repo: &Repository, remote_name: &str, url: &str) -> Result<(), Error> {
+    if remote_url(repo, remote_name)?.is_some() {
+        repo.remote_set_url(remote_name, url)
+    } else {
+        repo.remote(remote_name, url).map(|_| ())
+    }
+}
+
+/// Get owner and repository name from a github remote url
+///
+/// Supports these formats:
+/// git@github.com:owner/name.git
+/// ssh://git@github.com/owner/name.git
+/// https://github.com/owner/name(.git)
+pub fn parse_owner_and_name(url: &str) -> Option<(String, String)> {
+    let path = if let Some(i) = url.find("://") {
+        let rest = &url[i + 3..];
+        &rest[rest.find('/')? + 1..]
+    } else {
+        &url[url.find(':')? + 1..]
+    };
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+
+    let mut parts = path.split('/');
+    let owner = parts.next()?;
+    let name = parts.next()?;
+
+    if owner.is_empty() || name.is_empty() || parts.next().is_some() {
+        return None;
+    }
+
+    Some((owner.to_string(), name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_owner_and_name() {
+        let expected = Some(("divvun".to_string(), "gut".to_string()));
+        assert_eq!(
+            parse_owner_and_name("git@github.com:divvun/gut.git"),
+            expected
+        );
+        assert_eq!(
+            parse_owner_and_name("ssh://git@github.com/divvun/gut.git"),
+            expected
+        );
+        assert_eq!(
+            parse_owner_and_name("https://github.com/divvun/gut.git"),
+            expected
+        );
+        assert_eq!(
+            parse_owner_and_name("https://github.com/divvun/gut"),
+            expected
+        );
+        assert_eq!(
+            parse_owner_and_name("https://github.com/divvun/gut/"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_url() {
+        assert_eq!(parse_owner_and_name("https://github.com/divvun"), None);
+        assert_eq!(parse_owner_and_name("/home/user/gut"), None);
+        assert_eq!(parse_owner_and_name("https://github.com/a/b/c"), None);
+    }
+}
@@ -1,13 +1,39 @@
 use anyhow::Result;
 use git2::{Oid, Repository};
 
+#[derive(Debug, Clone)]
+pub struct StashEntry {
+    pub index: usize,
+    pub message: String,
+}
+
 pub fn stash(repo: &mut Repository, msg: Option<&str>) -> Result<Oid> {
     let sig = repo.signature()?;
     let oid = repo.stash_save2(&sig, msg, None)?;
     Ok(oid)
 }
 
-//pub fn apply(repo: &mut Repository) -> Result<()> {
-//repo.stash_apply(0, None)?;
-//Ok(())
-//}
+pub fn stash_list(repo: &mut Repository) -> Result<Vec<StashEntry>> {
+    let mut entries = vec![];
+    repo.stash_foreach(|index, message, _| {
+        entries.push(StashEntry {
+            index,
+            message: message.to_string(),
+        });
+        true
+    })?;
+    Ok(entries)
+}
+
+/// Apply a stash and remove it from the stash list
+///
+/// The stash is kept if it cannot be applied cleanly
+pub fn stash_pop(repo: &mut Repository, index: usize) -> Result<()> {
+    repo.stash_pop(index, None)?;
+    Ok(())
+}
+
+pub fn stash_drop(repo: &mut Repository, index: usize) -> Result<()> {
+    repo.stash_drop(index)?;
+    Ok(())
+}
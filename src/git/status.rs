@@ -1,4 +1,4 @@
-use git2::{Error, Repository, Status, StatusOptions};
+use git2::{Error, Repository, RepositoryState, Status, StatusOptions};
 
 #[derive(Debug)]
 pub struct GitStatus {
@@ -23,6 +23,16 @@ impl GitStatus {
             && self.added.is_empty()
     }
 
+    /// True if there are changes to tracked files, untracked files are ignored
+    pub fn has_tracked_changes(&self) -> bool {
+        !(self.modified.is_empty()
+            && self.deleted.is_empty()
+            && self.renamed.is_empty()
+            && self.typechanges.is_empty()
+            && self.conflicted.is_empty()
+            && self.added.is_empty())
+    }
+
     pub fn can_commit(&self) -> bool {
         self.conflicted.is_empty()
     }
@@ -47,7 +57,9 @@ impl GitStatus {
     }
 
     pub fn ahead_behind(&self) -> String {
-        if self.is_ahead > 0 {
+        if self.is_ahead > 0 && self.is_behind > 0 {
+            format!("{}/-{}", self.is_ahead, self.is_behind)
+        } else if self.is_ahead > 0 {
             format!("{}", self.is_ahead)
         } else if self.is_behind > 0 {
             format!("-{}", self.is_behind)
@@ -55,10 +67,6 @@ impl GitStatus {
             format!("{}", 0)
         }
     }
-
-    pub fn should_push(&self) -> bool {
-        self.is_ahead > 0
-    }
 }
 
 pub fn status(repo: &Repository, recurse_untracked_dirs: bool) -> Result<GitStatus, Error> {
@@ -154,3 +162,29 @@ pub fn status(repo: &Repository, recurse_untracked_dirs: bool) -> Result<GitStat
 
     Ok(status)
 }
+
+/// Name of the git operation in progress, None if there is nothing in progress
+pub fn operation_in_progress(repo: &Repository) -> Option<&'static str> {
+    match repo.state() {
+        RepositoryState::Clean => None,
+        RepositoryState::Merge => Some("merging"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some("reverting"),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some("cherry-picking"),
+        RepositoryState::Bisect => Some("bisecting"),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge => Some("rebasing"),
+        RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+            Some("applying patches")
+        }
+    }
+}
+
+/// Number of commits HEAD is ahead and behind of a revision
+///
+/// Return None if HEAD or the revision cannot be resolved
+pub fn ahead_behind(repo: &Repository, revision: &str) -> Option<(usize, usize)> {
+    let head = repo.revparse_single("HEAD").ok()?.peel_to_commit().ok()?;
+    let other = repo.revparse_single(revision).ok()?.peel_to_commit().ok()?;
+    repo.graph_ahead_behind(head.id(), other.id()).ok()
+}
@@ -0,0 +1,83 @@
+use git2::{Error, ObjectType, Oid, Repository, Sort};
+use std::collections::HashMap;
+
+/// Create a tag at HEAD, an annotated tag if there is a message otherwise a lightweight tag
+pub fn create_tag(
+    repo: &Repository,
+    name: &str,
+    message: Option<&str>,
+    force: bool,
+) -> Result<Oid, Error> {
+    let head = repo.head()?.peel(ObjectType::Commit)?;
+    match message {
+        Some(msg) => {
+            let sig = repo.signature()?;
+            repo.tag(name, &head, &sig, msg, force)
+        }
+        None => repo.tag_lightweight(name, &head, force),
+    }
+}
+
+pub fn delete_tag(repo: &Repository, name: &str) -> Result<(), Error> {
+    repo.tag_delete(name)
+}
+
+pub fn tag_names(repo: &Repository) -> Result<Vec<String>, Error> {
+    let names = repo.tag_names(None)?;
+    Ok(names.iter().flatten().map(|n| n.to_string()).collect())
+}
+
+/// The latest tag reachable from HEAD and the number of commits since that tag
+///
+/// Return None if no tag is reachable from HEAD
+pub fn latest_tag(repo: &Repository) -> Result<Option<(String, usize)>, Error> {
+    let head = repo.head()?.peel_to_commit()?;
+    nearest_tag(repo, head.id(), None)
+}
+
+/// The latest tag before `tag`, the nearest tag reachable from `tag` that points at another commit
+///
+/// Return None if there is no such tag
+pub fn previous_tag(repo: &Repository, tag: &str) -> Result<Option<String>, Error> {
+    let commit = tag_commit(repo, tag)?;
+    let previous = nearest_tag(repo, commit, Some(commit))?;
+    Ok(previous.map(|(name, _)| name))
+}
+
+/// The commit a tag points at
+pub fn tag_commit(repo: &Repository, tag: &str) -> Result<Oid, Error> {
+    let obj = repo.revparse_single(&format!("refs/tags/{}", tag))?;
+    Ok(obj.peel_to_commit()?.id())
+}
+
+fn nearest_tag(
+    repo: &Repository,
+    from: Oid,
+    exclude: Option<Oid>,
+) -> Result<Option<(String, usize)>, Error> {
+    let mut tagged: HashMap<Oid, String> = HashMap::new();
+    for name in tag_names(repo)? {
+        let obj = repo.revparse_single(&format!("refs/tags/{}", name))?;
+        if let Ok(commit) = obj.peel_to_commit() {
+            if Some(commit.id()) != exclude {
+                tagged.entry(commit.id()).or_insert(name);
+            }
+        }
+    }
+
+    if tagged.is_empty() {
+        return Ok(None);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(from)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    for (count, oid) in revwalk.enumerate() {
+        if let Some(name) = tagged.get(&oid?) {
+            return Ok(Some((name.to_string(), count)));
+        }
+    }
+
+    Ok(None)
+}
@@ -46,6 +46,95 @@ struct OrganizationRepositoriesWithTopics;
 )]
 struct OrganizationMembers;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "github.graphql",
+    query_path = "user_query.graphql",
+    response_derives = "Debug"
+)]
+struct UserRepositories;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "github.graphql",
+    query_path = "user_query.graphql",
+    response_derives = "Debug"
+)]
+struct UserRepositoriesWithTopics;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "github.graphql",
+    query_path = "user_query.graphql",
+    response_derives = "Debug"
+)]
+struct OwnerPullRequests;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "github.graphql",
+    query_path = "user_query.graphql",
+    response_derives = "Debug"
+)]
+struct RepositoryPullRequests;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "github.graphql",
+    query_path = "user_query.graphql",
+    response_derives = "Debug"
+)]
+struct TeamMembers;
+
+/// Both pull request queries select the same fields, but graphql_client generates separate types
+/// for each of them
+macro_rules! to_pull_request_status {
+    ($module:ident, $repo:expr, $pr:expr) => {{
+        use $module::{MergeableState, PullRequestReviewDecision, StatusState};
+
+        let pr = $pr;
+        let mergeable = match pr.mergeable {
+            MergeableState::MERGEABLE => MergeState::Mergeable,
+            MergeableState::CONFLICTING => MergeState::Conflicting,
+            _ => MergeState::Unknown,
+        };
+        let review = match pr.review_decision {
+            Some(PullRequestReviewDecision::APPROVED) => Some(ReviewDecision::Approved),
+            Some(PullRequestReviewDecision::CHANGES_REQUESTED) => {
+                Some(ReviewDecision::ChangesRequested)
+            }
+            Some(PullRequestReviewDecision::REVIEW_REQUIRED) => {
+                Some(ReviewDecision::ReviewRequired)
+            }
+            _ => None,
+        };
+        let checks = pr
+            .commits
+            .nodes
+            .as_ref()
+            .and_then(|nodes| nodes.iter().flatten().last())
+            .and_then(|node| node.commit.status_check_rollup.as_ref())
+            .map(|rollup| match rollup.state {
+                StatusState::SUCCESS => CheckState::Success,
+                StatusState::PENDING | StatusState::EXPECTED => CheckState::Pending,
+                _ => CheckState::Failure,
+            });
+
+        PullRequestStatus {
+            repo: $repo.to_string(),
+            number: pr.number,
+            title: pr.title.to_string(),
+            head: pr.head_ref_name.to_string(),
+            base: pr.base_ref_name.to_string(),
+            author: pr.author.as_ref().map(|a| a.login.to_string()),
+            draft: pr.is_draft,
+            mergeable,
+            review,
+            checks,
+        }
+    }};
+}
+
 fn query<T: Serialize + ?Sized>(token: &str, body: &T) -> Result<req::Response, reqwest::Error> {
     let client = req::Client::new();
     client
@@ -150,6 +239,86 @@ fn get_org_members_rec(
     Ok(list_member)
 }
 
+#[derive(Debug, PartialEq)]
+pub struct TeamMember {
+    pub login: String,
+    /// Either member or maintainer
+    pub role: String,
+}
+
+/// List members of a team, without the members of its child teams
+pub fn get_immediate_team_members(
+    org: &str,
+    slug: &str,
+    token: &str,
+) -> anyhow::Result<Vec<TeamMember>> {
+    get_immediate_team_members_rec(org, slug, token, None)
+}
+
+fn get_immediate_team_members_rec(
+    org: &str,
+    slug: &str,
+    token: &str,
+    after: Option<String>,
+) -> anyhow::Result<Vec<TeamMember>> {
+    let q = TeamMembers::build_query(team_members::Variables {
+        org: org.to_string(),
+        slug: slug.to_string(),
+        after,
+    });
+
+    let res = query(token, &q)?;
+
+    let response_status = res.status();
+    if response_status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(Unauthorized.into());
+    }
+
+    let response_body: Response<team_members::ResponseData> = res.json()?;
+
+    let members = &response_body
+        .data
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .organization
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .team
+        .as_ref()
+        .ok_or(NoTeamFound)?
+        .members;
+
+    let mut list_member = to_team_members(members);
+
+    let page_info = &members.page_info;
+
+    if page_info.has_next_page {
+        let after = page_info.end_cursor.as_ref().map(|x| x.to_string());
+        let mut rest = get_immediate_team_members_rec(org, slug, token, after)?;
+        list_member.append(&mut rest);
+    }
+    Ok(list_member)
+}
+
+fn to_team_members(members: &team_members::TeamMembersOrganizationTeamMembers) -> Vec<TeamMember> {
+    use team_members::TeamMemberRole;
+
+    members
+        .edges
+        .iter()
+        .flatten()
+        .filter_map(|edge| edge.as_ref())
+        .map(|edge| TeamMember {
+            login: edge.node.login.to_string(),
+            role: match edge.role {
+                TeamMemberRole::MAINTAINER => "maintainer",
+                _ => "member",
+            }
+            .to_string(),
+        })
+        .collect()
+}
+
 fn list_org_repos_rec(
     token: &str,
     org: &str,
@@ -188,6 +357,7 @@ fn list_org_repos_rec(
             ssh_url: x.ssh_url.to_string(),
             owner: org.to_string(),
             https_url: x.url.to_string(),
+            default_branch: x.default_branch_ref.as_ref().map(|b| b.name.to_string()),
         })
         .collect();
 
@@ -250,6 +420,7 @@ fn list_org_repos_with_topics_rec(
                 ssh_url: x.ssh_url.to_string(),
                 owner: org.to_string(),
                 https_url: x.url.to_string(),
+                default_branch: x.default_branch_ref.as_ref().map(|b| b.name.to_string()),
             },
             topics: x
                 .repository_topics
@@ -282,6 +453,282 @@ pub fn list_org_repos_with_topics(
     list_org_repos_with_topics_rec(token, org, None)
 }
 
+fn list_user_repos_rec(
+    token: &str,
+    user: &str,
+    after: Option<String>,
+) -> anyhow::Result<Vec<RemoteRepo>> {
+    let q = UserRepositories::build_query(user_repositories::Variables {
+        login: user.to_string(),
+        after,
+    });
+
+    let res = query(token, &q)?;
+
+    let response_status = res.status();
+    if response_status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(Unauthorized.into());
+    }
+
+    let response_body: Response<user_repositories::ResponseData> = res.json()?;
+
+    let user_data = response_body
+        .data
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .user
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?;
+
+    let repositories = user_data.repositories.nodes.as_ref();
+
+    let mut list_repo: Vec<RemoteRepo> = repositories
+        .ok_or(NoReposFound)?
+        .iter()
+        .filter_map(|repo| repo.as_ref())
+        .map(|x| RemoteRepo {
+            name: x.name.to_string(),
+            ssh_url: x.ssh_url.to_string(),
+            owner: user.to_string(),
+            https_url: x.url.to_string(),
+            default_branch: x.default_branch_ref.as_ref().map(|b| b.name.to_string()),
+        })
+        .collect();
+
+    let page_info = &user_data.repositories.page_info;
+
+    if page_info.has_next_page {
+        let after = page_info.end_cursor.as_ref().map(|x| x.to_string());
+        match list_user_repos_rec(token, user, after) {
+            Ok(mut l) => list_repo.append(&mut l),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(list_repo)
+}
+
+pub fn list_user_repos(token: &str, user: &str) -> anyhow::Result<Vec<RemoteRepo>> {
+    list_user_repos_rec(token, user, None)
+}
+
+fn list_user_repos_with_topics_rec(
+    token: &str,
+    user: &str,
+    after: Option<String>,
+) -> anyhow::Result<Vec<RemoteRepoWithTopics>> {
+    let q = UserRepositoriesWithTopics::build_query(user_repositories_with_topics::Variables {
+        login: user.to_string(),
+        after,
+    });
+
+    let res = query(token, &q)?;
+
+    let response_status = res.status();
+    if response_status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(Unauthorized.into());
+    }
+
+    let response_body: Response<user_repositories_with_topics::ResponseData> = res.json()?;
+
+    let user_data = response_body
+        .data
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .user
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?;
+
+    let repositories = user_data.repositories.nodes.as_ref();
+
+    let temp = vec![];
+    let mut list_repo: Vec<RemoteRepoWithTopics> = repositories
+        .ok_or(NoReposFound)?
+        .iter()
+        .filter_map(|repo| repo.as_ref())
+        .map(|x| RemoteRepoWithTopics {
+            repo: RemoteRepo {
+                name: x.name.to_string(),
+                ssh_url: x.ssh_url.to_string(),
+                owner: user.to_string(),
+                https_url: x.url.to_string(),
+                default_branch: x.default_branch_ref.as_ref().map(|b| b.name.to_string()),
+            },
+            topics: x
+                .repository_topics
+                .nodes
+                .as_ref()
+                .unwrap_or(&temp)
+                .iter()
+                .filter_map(|t| t.as_ref())
+                .map(|x| x.topic.name.to_string())
+                .collect(),
+        })
+        .collect();
+
+    let page_info = &user_data.repositories.page_info;
+
+    if page_info.has_next_page {
+        let after = page_info.end_cursor.as_ref().map(|x| x.to_string());
+        match list_user_repos_with_topics_rec(token, user, after) {
+            Ok(mut l) => list_repo.append(&mut l),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(list_repo)
+}
+
+pub fn list_user_repos_with_topics(
+    token: &str,
+    user: &str,
+) -> anyhow::Result<Vec<RemoteRepoWithTopics>> {
+    list_user_repos_with_topics_rec(token, user, None)
+}
+
+/// List repositories of an owner, which can be either an organisation or a user account
+pub fn list_owner_repos(token: &str, owner: &str) -> anyhow::Result<Vec<RemoteRepo>> {
+    match super::get_owner_type(owner, token)? {
+        OwnerType::Organisation => list_org_repos(token, owner),
+        OwnerType::User => list_user_repos(token, owner),
+    }
+}
+
+/// List repositories with their topics of an owner, which can be either an organisation or a
+/// user account
+pub fn list_owner_repos_with_topics(
+    token: &str,
+    owner: &str,
+) -> anyhow::Result<Vec<RemoteRepoWithTopics>> {
+    match super::get_owner_type(owner, token)? {
+        OwnerType::Organisation => list_org_repos_with_topics(token, owner),
+        OwnerType::User => list_user_repos_with_topics(token, owner),
+    }
+}
+
+/// List open pull requests of all repositories of an owner, optionally only for a head branch
+///
+/// Pull requests of 50 repositories are fetched in one query
+pub fn list_open_pull_requests(
+    token: &str,
+    owner: &str,
+    head: Option<&str>,
+) -> anyhow::Result<Vec<PullRequestStatus>> {
+    list_open_pull_requests_rec(token, owner, head, None)
+}
+
+fn list_open_pull_requests_rec(
+    token: &str,
+    owner: &str,
+    head: Option<&str>,
+    after: Option<String>,
+) -> anyhow::Result<Vec<PullRequestStatus>> {
+    use owner_pull_requests::Variables;
+
+    let q = OwnerPullRequests::build_query(Variables {
+        login: owner.to_string(),
+        after,
+        head: head.map(|h| h.to_string()),
+    });
+
+    let res = query(token, &q)?;
+
+    let response_status = res.status();
+    if response_status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(Unauthorized.into());
+    }
+
+    let response_body: Response<owner_pull_requests::ResponseData> = res.json()?;
+
+    let owner_data = response_body
+        .data
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .repository_owner
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?;
+
+    let repositories = owner_data.repositories.nodes.as_ref();
+
+    let mut list_pr = vec![];
+    for repo in repositories
+        .ok_or(NoReposFound)?
+        .iter()
+        .filter_map(|repo| repo.as_ref())
+    {
+        let prs = repo.pull_requests.nodes.as_ref();
+        for pr in prs.into_iter().flatten().filter_map(|pr| pr.as_ref()) {
+            list_pr.push(to_pull_request_status!(owner_pull_requests, repo.name, pr));
+        }
+
+        let page_info = &repo.pull_requests.page_info;
+        if page_info.has_next_page {
+            let after = page_info.end_cursor.as_ref().map(|x| x.to_string());
+            let mut rest = list_repo_pull_requests_rec(token, owner, &repo.name, head, after)?;
+            list_pr.append(&mut rest);
+        }
+    }
+
+    let page_info = &owner_data.repositories.page_info;
+
+    if page_info.has_next_page {
+        let after = page_info.end_cursor.as_ref().map(|x| x.to_string());
+        match list_open_pull_requests_rec(token, owner, head, after) {
+            Ok(mut l) => list_pr.append(&mut l),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(list_pr)
+}
+
+fn list_repo_pull_requests_rec(
+    token: &str,
+    owner: &str,
+    name: &str,
+    head: Option<&str>,
+    after: Option<String>,
+) -> anyhow::Result<Vec<PullRequestStatus>> {
+    let q = RepositoryPullRequests::build_query(repository_pull_requests::Variables {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        after,
+        head: head.map(|h| h.to_string()),
+    });
+
+    let res = query(token, &q)?;
+
+    let response_status = res.status();
+    if response_status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(Unauthorized.into());
+    }
+
+    let response_body: Response<repository_pull_requests::ResponseData> = res.json()?;
+
+    let pull_requests = &response_body
+        .data
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .repository
+        .as_ref()
+        .ok_or(InvalidRepoResponse)?
+        .pull_requests;
+
+    let mut list_pr: Vec<_> = pull_requests
+        .nodes
+        .iter()
+        .flatten()
+        .filter_map(|pr| pr.as_ref())
+        .map(|pr| to_pull_request_status!(repository_pull_requests, name, pr))
+        .collect();
+
+    let page_info = &pull_requests.page_info;
+
+    if page_info.has_next_page {
+        let after = page_info.end_cursor.as_ref().map(|x| x.to_string());
+        let mut rest = list_repo_pull_requests_rec(token, owner, name, head, after)?;
+        list_pr.append(&mut rest);
+    }
+    Ok(list_pr)
+}
+
 #[allow(dead_code)]
 pub fn default_branch(repo: &RemoteRepo, token: &str) -> anyhow::Result<String> {
     let q = RepositoryDefaultBranch::build_query(repository_default_branch::Variables {
@@ -315,3 +762,52 @@ pub fn default_branch(repo: &RemoteRepo, token: &str) -> anyhow::Result<String>
     log::debug!("Default branch of repository {} is: {}", repo.name, branch);
     Ok(branch.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_team_members() {
+        let q = TeamMembers::build_query(team_members::Variables {
+            org: "org".to_string(),
+            slug: "linguists".to_string(),
+            after: None,
+        });
+        assert!(q.query.contains("membership: IMMEDIATE"));
+
+        let response: Response<team_members::ResponseData> = serde_json::from_str(
+            r#"{"data": {"organization": {"team": {"members": {
+                "edges": [
+                    {"role": "MEMBER", "node": {"login": "anna"}},
+                    null,
+                    {"role": "MAINTAINER", "node": {"login": "bo"}}
+                ],
+                "pageInfo": {"endCursor": null, "hasNextPage": false}
+            }}}}}"#,
+        )
+        .unwrap();
+        let members = &response
+            .data
+            .unwrap()
+            .organization
+            .unwrap()
+            .team
+            .unwrap()
+            .members;
+
+        assert_eq!(
+            to_team_members(members),
+            vec![
+                TeamMember {
+                    login: "anna".to_string(),
+                    role: "member".to_string()
+                },
+                TeamMember {
+                    login: "bo".to_string(),
+                    role: "maintainer".to_string()
+                },
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use reqwest::StatusCode;
+use serde::Deserialize;
 use std::cmp::Ord;
 use std::cmp::Ordering;
 
@@ -8,6 +9,7 @@ pub struct RemoteRepo {
     pub owner: String,
     pub ssh_url: String,
     pub https_url: String,
+    pub default_branch: Option<String>,
 }
 
 impl RemoteRepo {
@@ -56,6 +58,13 @@ impl PartialEq for RemoteRepoWithTopics {
     }
 }
 
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum OwnerType {
+    #[serde(rename = "Organization")]
+    Organisation,
+    User,
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("User unauthorized")]
 pub struct Unauthorized;
@@ -76,6 +85,67 @@ pub struct NoReposFound;
 #[error("no members found")]
 pub struct NoMembersFound;
 
+#[derive(thiserror::Error, Debug)]
+#[error("no team found")]
+pub struct NoTeamFound;
+
 #[derive(thiserror::Error, Debug)]
 #[error("No default branch")]
 pub struct NoDefaultBranch;
+
+#[derive(thiserror::Error, Debug)]
+#[error("{0} is a user account, not an organisation. This command only works with organisations")]
+pub struct NotAnOrganisation(pub String);
+
+/// An open pull request with its merge, review and check state
+#[derive(Debug, Clone)]
+pub struct PullRequestStatus {
+    pub repo: String,
+    pub number: i64,
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    pub author: Option<String>,
+    pub draft: bool,
+    pub mergeable: MergeState,
+    /// None if no review is required
+    pub review: Option<ReviewDecision>,
+    /// Combined state of statuses and check runs of the head commit, None if there is none
+    pub checks: Option<CheckState>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeState {
+    Mergeable,
+    Conflicting,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckState {
+    Success,
+    Pending,
+    Failure,
+}
+
+impl PullRequestStatus {
+    /// Approved, all checks passed and no conflicts
+    pub fn is_ready(&self) -> bool {
+        !self.draft
+            && self.mergeable == MergeState::Mergeable
+            && self.is_approved()
+            && self.checks == Some(CheckState::Success)
+    }
+
+    /// GitHub has no review decision for repositories that don't require reviews
+    pub fn is_approved(&self) -> bool {
+        matches!(self.review, None | Some(ReviewDecision::Approved))
+    }
+}
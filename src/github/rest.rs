@@ -89,6 +89,8 @@ struct UpdateRepoBody {
     homepage: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
+    #[serde(flatten)]
+    settings: RepoSettings,
 }
 
 impl UpdateRepoBody {
@@ -99,6 +101,7 @@ impl UpdateRepoBody {
             description: None,
             homepage: None,
             name: None,
+            settings: RepoSettings::default(),
         }
     }
 
@@ -109,6 +112,7 @@ impl UpdateRepoBody {
             description: None,
             homepage: None,
             name: None,
+            settings: RepoSettings::default(),
         }
     }
 
@@ -119,6 +123,7 @@ impl UpdateRepoBody {
             description: des.map(|s| s.to_string()),
             homepage: homepage.map(|s| s.to_string()),
             name: None,
+            settings: RepoSettings::default(),
         }
     }
 
@@ -129,8 +134,72 @@ impl UpdateRepoBody {
             description: None,
             homepage: None,
             name: Some(name.to_string()),
+            settings: RepoSettings::default(),
         }
     }
+
+    fn settings(settings: &RepoSettings) -> UpdateRepoBody {
+        UpdateRepoBody {
+            default_branch: None,
+            private: None,
+            description: None,
+            homepage: None,
+            name: None,
+            settings: settings.clone(),
+        }
+    }
+}
+
+/// Merge options and features of a repository, None means the value is left untouched
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RepoSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_squash_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_merge_commit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_rebase_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_auto_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_branch_on_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_issues: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_wiki: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_discussions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_template: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_commit_signoff_required: Option<bool>,
+}
+
+impl RepoSettings {
+    /// Values of `other` that are set take precedence over the values of self
+    pub fn merge(&self, other: &RepoSettings) -> RepoSettings {
+        RepoSettings {
+            allow_squash_merge: other.allow_squash_merge.or(self.allow_squash_merge),
+            allow_merge_commit: other.allow_merge_commit.or(self.allow_merge_commit),
+            allow_rebase_merge: other.allow_rebase_merge.or(self.allow_rebase_merge),
+            allow_auto_merge: other.allow_auto_merge.or(self.allow_auto_merge),
+            delete_branch_on_merge: other.delete_branch_on_merge.or(self.delete_branch_on_merge),
+            has_issues: other.has_issues.or(self.has_issues),
+            has_wiki: other.has_wiki.or(self.has_wiki),
+            has_projects: other.has_projects.or(self.has_projects),
+            has_discussions: other.has_discussions.or(self.has_discussions),
+            is_template: other.is_template.or(self.is_template),
+            web_commit_signoff_required: other
+                .web_commit_signoff_required
+                .or(self.web_commit_signoff_required),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == RepoSettings::default()
+    }
 }
 
 pub fn set_default_branch(repo: &RemoteRepo, branch: &str, token: &str) -> Result<()> {
@@ -170,6 +239,27 @@ pub fn set_repo_metadata(
     process_response(&response).map(|_| ())
 }
 
+// https://docs.github.com/en/rest/reference/repos#update-a-repository
+pub fn set_repo_settings(repo: &RemoteRepo, settings: &RepoSettings, token: &str) -> Result<()> {
+    let url = format!("https://api.github.com/repos/{}/{}", repo.owner, repo.name);
+    let body = UpdateRepoBody::settings(settings);
+    let response = patch(&url, &body, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+// https://docs.github.com/en/rest/reference/repos#get-a-repository
+pub fn get_repo_settings(repo: &RemoteRepo, token: &str) -> Result<RepoSettings> {
+    let url = format!("https://api.github.com/repos/{}/{}", repo.owner, repo.name);
+
+    let response = get(&url, token, None)?;
+
+    process_response(&response)?;
+
+    let response_body: RepoSettings = response.json()?;
+    Ok(response_body)
+}
+
 #[derive(Serialize, Debug)]
 struct ProtectedBranch {
     required_status_checks: Option<RequiredStatusCheck>,
@@ -228,6 +318,22 @@ pub fn set_protected_branch(repo: &RemoteRepo, branch: &str, token: &str) -> Res
     process_response(&response).map(|_| ())
 }
 
+// https://docs.github.com/en/rest/reference/repos#get-branch-protection
+pub fn is_branch_protected(repo: &RemoteRepo, branch: &str, token: &str) -> Result<bool> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/branches/{}/protection",
+        repo.owner, repo.name, branch
+    );
+
+    let response = get(&url, token, None)?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+
+    process_response(&response).map(|_| true)
+}
+
 pub fn set_unprotected_branch(repo: &RemoteRepo, branch: &str, token: &str) -> Result<()> {
     let url = format!(
         "https://api.github.com/repos/{}/{}/branches/{}/protection",
@@ -245,6 +351,7 @@ pub fn create_team(
     description: &str,
     maintainers: Vec<String>,
     is_secret: bool,
+    parent: Option<i32>,
     token: &str,
 ) -> Result<CreateTeamResponse> {
     let url = format!("https://api.github.com/orgs/{}/teams", org);
@@ -258,6 +365,7 @@ pub fn create_team(
         description: description.to_string(),
         maintainers,
         privacy,
+        parent_team_id: parent,
     };
 
     let response = post(&url, &body, token)?;
@@ -282,11 +390,14 @@ struct CreateTeamBody {
     description: String,
     maintainers: Vec<String>,
     privacy: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_team_id: Option<i32>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct CreateTeamResponse {
     pub id: i32,
+    pub slug: String,
     pub html_url: String,
 }
 
@@ -325,14 +436,31 @@ pub fn add_user_to_team(org: &str, team: &str, role: &str, user: &str, token: &s
     process_response(&response).map(|_| ())
 }
 
+// https://docs.github.com/en/rest/reference/teams#list-teams
 pub fn get_teams(org: &str, token: &str) -> Result<Vec<Team>> {
-    let url = format!("https://api.github.com/orgs/{}/teams", org);
+    let mut teams = vec![];
+    let per_page = 100;
 
-    let response = get(&url, token, None)?;
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/orgs/{}/teams?per_page={}&page={}",
+            org, per_page, page
+        );
 
-    process_response(&response).map(|_| ())?;
+        let response = get(&url, token, None)?;
 
-    response.json().map_err(Into::into)
+        process_response(&response)?;
+
+        let response_body: Vec<Team> = response.json()?;
+        let count = response_body.len();
+        teams.extend(response_body);
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(teams)
 }
 
 #[derive(Deserialize, Debug)]
@@ -340,6 +468,177 @@ pub struct Team {
     pub id: i32,
     pub slug: String,
     pub name: String,
+    pub parent: Option<ParentTeam>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ParentTeam {
+    pub id: i32,
+}
+
+// https://docs.github.com/en/rest/reference/teams#list-team-members
+// role is one of all, member or maintainer
+pub fn get_team_members(org: &str, team: &str, role: &str, token: &str) -> Result<Vec<String>> {
+    let mut members = vec![];
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/orgs/{}/teams/{}/members?role={}&per_page={}&page={}",
+            org, team, role, per_page, page
+        );
+
+        let response = get(&url, token, None)?;
+
+        process_response(&response)?;
+
+        let response_body: Vec<Member> = response.json()?;
+        let count = response_body.len();
+        members.extend(response_body.into_iter().map(|m| m.login));
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(members)
+}
+
+#[derive(Deserialize, Debug)]
+struct Member {
+    login: String,
+}
+
+// https://docs.github.com/en/rest/reference/teams#list-team-repositories
+pub fn get_team_repos(org: &str, team: &str, token: &str) -> Result<Vec<TeamRepo>> {
+    let mut repos = vec![];
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/orgs/{}/teams/{}/repos?per_page={}&page={}",
+            org, team, per_page, page
+        );
+
+        let response = get(&url, token, None)?;
+
+        process_response(&response)?;
+
+        let response_body: Vec<TeamRepo> = response.json()?;
+        let count = response_body.len();
+        repos.extend(response_body);
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(repos)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamRepo {
+    pub name: String,
+    pub permissions: RepoPermissions,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RepoPermissions {
+    pub admin: bool,
+    #[serde(default)]
+    pub maintain: bool,
+    pub push: bool,
+    #[serde(default)]
+    pub triage: bool,
+}
+
+impl TeamRepo {
+    /// The highest permission the team has on this repository
+    pub fn permission(&self) -> &'static str {
+        self.permissions.highest()
+    }
+}
+
+impl RepoPermissions {
+    pub fn highest(&self) -> &'static str {
+        let p = self;
+        if p.admin {
+            "admin"
+        } else if p.maintain {
+            "maintain"
+        } else if p.push {
+            "push"
+        } else if p.triage {
+            "triage"
+        } else {
+            "pull"
+        }
+    }
+}
+
+// https://docs.github.com/en/rest/reference/repos#list-repository-collaborators
+// affiliation is one of all, direct or outside
+pub fn get_collaborators(
+    repo: &RemoteRepo,
+    affiliation: &str,
+    token: &str,
+) -> Result<Vec<Collaborator>> {
+    let mut collaborators = vec![];
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/collaborators?affiliation={}&per_page={}&page={}",
+            repo.owner, repo.name, affiliation, per_page, page
+        );
+
+        let response = get(&url, token, None)?;
+
+        process_response(&response)?;
+
+        let response_body: Vec<Collaborator> = response.json()?;
+        let count = response_body.len();
+        collaborators.extend(response_body);
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(collaborators)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Collaborator {
+    pub login: String,
+    pub permissions: RepoPermissions,
+}
+
+// https://docs.github.com/en/rest/reference/orgs#list-organization-members
+pub fn get_org_owners(org: &str, token: &str) -> Result<Vec<String>> {
+    let mut owners = vec![];
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/orgs/{}/members?role=admin&per_page={}&page={}",
+            org, per_page, page
+        );
+
+        let response = get(&url, token, None)?;
+
+        process_response(&response)?;
+
+        let response_body: Vec<Member> = response.json()?;
+        let count = response_body.len();
+        owners.extend(response_body.into_iter().map(|m| m.login));
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(owners)
 }
 
 pub fn invite_user_to_org(
@@ -490,6 +789,23 @@ pub fn create_org_repo(
     Ok(response_body)
 }
 
+// https://docs.github.com/en/rest/reference/repos#create-a-repository-for-the-authenticated-user
+pub fn create_user_repo(name: &str, public: bool, token: &str) -> Result<CreateRepoResponse> {
+    let url = "https://api.github.com/user/repos";
+
+    let body = CreateRepoBody {
+        name: name.to_string(),
+        private: !public,
+    };
+
+    let response = post(url, &body, token)?;
+
+    process_response(&response)?;
+
+    let response_body: CreateRepoResponse = response.json()?;
+    Ok(response_body)
+}
+
 #[derive(Serialize, Debug)]
 struct CreateRepoBody {
     name: String,
@@ -504,6 +820,26 @@ pub struct CreateRepoResponse {
     pub clone_url: String,
 }
 
+// https://docs.github.com/en/rest/reference/repos#get-a-repository
+// This follows redirects, so a renamed or transferred repository returns its new name
+pub fn get_repo(owner: &str, repo: &str, token: &str) -> Result<RepoResponse> {
+    let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+
+    let response = get(&url, token, None)?;
+
+    process_response(&response)?;
+
+    let response_body: RepoResponse = response.json()?;
+    Ok(response_body)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RepoResponse {
+    pub full_name: String,
+    pub private: bool,
+    pub description: Option<String>,
+}
+
 pub fn delete_repo(owner: &str, repo: &str, token: &str) -> Result<()> {
     let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
 
@@ -768,6 +1104,18 @@ pub fn add_repo_to_team(
     process_response(&response).map(|_| ())
 }
 
+// https://docs.github.com/en/rest/reference/teams#remove-a-repository-from-a-team
+pub fn remove_repo_from_team(repo: &RemoteRepo, team: &str, token: &str) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/orgs/{}/teams/{}/repos/{}/{}",
+        repo.owner, team, repo.owner, repo.name
+    );
+
+    let response = delete(&url, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
 #[derive(Serialize, Debug)]
 struct SetRepoToTeamBody {
     permission: String,
@@ -874,6 +1222,572 @@ struct DispatchBody {
     event_type: String,
 }
 
+// https://docs.github.com/en/rest/reference/repos#create-a-release
+pub fn create_release(
+    repo: &RemoteRepo,
+    release: &CreateReleaseBody,
+    token: &str,
+) -> Result<CreateReleaseResponse> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/releases",
+        repo.owner, repo.name
+    );
+
+    let response = post(&url, release, token)?;
+
+    process_response(&response)?;
+
+    let response_body: CreateReleaseResponse = response.json()?;
+    Ok(response_body)
+}
+
+#[derive(Serialize, Debug)]
+pub struct CreateReleaseBody {
+    pub tag_name: String,
+    pub target_commitish: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub generate_release_notes: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateReleaseResponse {
+    pub html_url: String,
+    pub upload_url: String,
+}
+
+// https://docs.github.com/en/rest/reference/repos#upload-a-release-asset
+pub fn upload_release_asset(
+    release: &CreateReleaseResponse,
+    name: &str,
+    content: Vec<u8>,
+    token: &str,
+) -> Result<()> {
+    // upload_url is a hypermedia template like ".../assets{?name,label}"
+    let url = release
+        .upload_url
+        .split('{')
+        .next()
+        .unwrap_or(&release.upload_url);
+    log::debug!("POST: {} with asset {}", url, name);
+
+    let client = req::Client::new();
+    let response = client
+        .post(url)
+        .query(&[("name", name)])
+        .bearer_auth(token)
+        .header("User-Agent", super::USER_AGENT)
+        .header("Accept", "application/vnd.github.v3+json")
+        .header("Content-Type", "application/octet-stream")
+        .body(content)
+        .send()?;
+
+    process_response(&response).map(|_| ())
+}
+
+// https://docs.github.com/en/rest/reference/repos#compare-two-commits
+// Return None if one of the branches doesn't exist
+pub fn compare_branches(
+    repo: &RemoteRepo,
+    base: &str,
+    head: &str,
+    token: &str,
+) -> Result<Option<CompareResponse>> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/compare/{}...{}",
+        repo.owner, repo.name, base, head
+    );
+
+    let response = get(&url, token, None)?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    process_response(&response)?;
+
+    let response_body: CompareResponse = response.json()?;
+    Ok(Some(response_body))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CompareResponse {
+    pub ahead_by: usize,
+}
+
+// https://docs.github.com/en/rest/reference/pulls#list-pull-requests
+pub fn get_open_pull_requests(
+    repo: &RemoteRepo,
+    head: Option<&str>,
+    token: &str,
+) -> Result<Vec<PullRequest>> {
+    let mut url = format!(
+        "https://api.github.com/repos/{}/{}/pulls?state=open&per_page=100",
+        repo.owner, repo.name
+    );
+    if let Some(head) = head {
+        url = format!("{}&head={}:{}", url, repo.owner, head);
+    }
+
+    let response = get(&url, token, None)?;
+
+    process_response(&response)?;
+
+    let response_body: Vec<PullRequest> = response.json()?;
+    Ok(response_body)
+}
+
+// https://docs.github.com/en/rest/reference/pulls#create-a-pull-request
+pub fn create_pull_request(
+    repo: &RemoteRepo,
+    pull_request: &CreatePullRequestBody,
+    token: &str,
+) -> Result<PullRequest> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls",
+        repo.owner, repo.name
+    );
+
+    let response = post(&url, pull_request, token)?;
+
+    process_response(&response)?;
+
+    let response_body: PullRequest = response.json()?;
+    Ok(response_body)
+}
+
+#[derive(Serialize, Debug)]
+pub struct CreatePullRequestBody {
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub draft: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PullRequest {
+    pub number: usize,
+    pub html_url: String,
+}
+
+// https://docs.github.com/en/rest/reference/pulls#request-reviewers-for-a-pull-request
+pub fn request_reviewers(
+    repo: &RemoteRepo,
+    number: usize,
+    reviewers: &[String],
+    team_reviewers: &[String],
+    token: &str,
+) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/requested_reviewers",
+        repo.owner, repo.name, number
+    );
+
+    let body = RequestReviewersBody {
+        reviewers: reviewers.to_owned(),
+        team_reviewers: team_reviewers.to_owned(),
+    };
+
+    let response = post(&url, &body, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+#[derive(Serialize, Debug)]
+struct RequestReviewersBody {
+    reviewers: Vec<String>,
+    team_reviewers: Vec<String>,
+}
+
+// https://docs.github.com/en/rest/reference/issues#add-labels-to-an-issue
+pub fn add_labels(repo: &RemoteRepo, number: usize, labels: &[String], token: &str) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/labels",
+        repo.owner, repo.name, number
+    );
+
+    let body = AddLabelsBody {
+        labels: labels.to_owned(),
+    };
+
+    let response = post(&url, &body, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+#[derive(Serialize, Debug)]
+struct AddLabelsBody {
+    labels: Vec<String>,
+}
+
+// https://docs.github.com/en/rest/reference/pulls#merge-a-pull-request
+pub fn merge_pull_request(
+    repo_name: &str,
+    owner: &str,
+    number: i64,
+    merge_method: &str,
+    token: &str,
+) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/merge",
+        owner, repo_name, number
+    );
+
+    let body = MergePullRequestBody {
+        merge_method: merge_method.to_string(),
+    };
+
+    let response = put(&url, &body, token, None)?;
+
+    process_response(&response).map(|_| ())
+}
+
+#[derive(Serialize, Debug)]
+struct MergePullRequestBody {
+    merge_method: String,
+}
+
+// https://docs.github.com/en/rest/reference/git#delete-a-reference
+pub fn delete_branch(repo_name: &str, owner: &str, branch: &str, token: &str) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/git/refs/heads/{}",
+        owner, repo_name, branch
+    );
+
+    let response = delete(&url, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+// https://docs.github.com/en/rest/reference/issues#create-an-issue
+pub fn create_issue(repo: &RemoteRepo, issue: &CreateIssueBody, token: &str) -> Result<Issue> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues",
+        repo.owner, repo.name
+    );
+
+    let response = post(&url, issue, token)?;
+
+    process_response(&response)?;
+
+    let response_body: Issue = response.json()?;
+    Ok(response_body)
+}
+
+#[derive(Serialize, Debug)]
+pub struct CreateIssueBody {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<usize>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Issue {
+    pub number: usize,
+    pub title: String,
+    pub html_url: String,
+    pub repository_url: String,
+    pub user: IssueUser,
+    pub labels: Vec<IssueLabel>,
+    pub assignees: Vec<IssueUser>,
+    pub created_at: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct IssueUser {
+    pub login: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct IssueLabel {
+    pub name: String,
+}
+
+impl Issue {
+    /// Name of the repository of this issue, taken from the repository url
+    pub fn repo_name(&self) -> &str {
+        self.repository_url
+            .rsplit('/')
+            .next()
+            .unwrap_or(&self.repository_url)
+    }
+}
+
+// https://docs.github.com/en/rest/reference/search#search-issues-and-pull-requests
+// The search api returns at most 1000 results
+pub fn search_issues(query: &str, token: &str) -> Result<Vec<Issue>> {
+    let mut issues = vec![];
+    let per_page = 100;
+
+    for page in 1..=10 {
+        let client = req::Client::new();
+        log::debug!("get: search issues {} page {}", query, page);
+        let response = client
+            .get("https://api.github.com/search/issues")
+            .query(&[
+                ("q", query),
+                ("per_page", &per_page.to_string()),
+                ("page", &page.to_string()),
+            ])
+            .bearer_auth(token)
+            .header("User-Agent", super::USER_AGENT)
+            .header("Accept", "application/vnd.github.v3+json")
+            .send()?;
+
+        process_response(&response)?;
+
+        let response_body: SearchIssuesResponse = response.json()?;
+        let count = response_body.items.len();
+        issues.extend(response_body.items);
+
+        if count < per_page || issues.len() >= response_body.total_count {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+#[derive(Deserialize, Debug)]
+struct SearchIssuesResponse {
+    total_count: usize,
+    items: Vec<Issue>,
+}
+
+// https://docs.github.com/en/rest/reference/issues#list-milestones
+pub fn get_milestones(repo: &RemoteRepo, token: &str) -> Result<Vec<Milestone>> {
+    let mut milestones = vec![];
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/milestones?state=all&per_page={}&page={}",
+            repo.owner, repo.name, per_page, page
+        );
+
+        let response = get(&url, token, None)?;
+
+        process_response(&response)?;
+
+        let response_body: Vec<Milestone> = response.json()?;
+        let count = response_body.len();
+        milestones.extend(response_body);
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(milestones)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Milestone {
+    pub number: usize,
+    pub title: String,
+    pub state: String,
+    pub open_issues: usize,
+    pub closed_issues: usize,
+    pub due_on: Option<String>,
+}
+
+// https://docs.github.com/en/rest/reference/issues#create-a-milestone
+pub fn create_milestone(
+    repo: &RemoteRepo,
+    title: &str,
+    description: Option<&str>,
+    due_on: Option<&str>,
+    token: &str,
+) -> Result<Milestone> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/milestones",
+        repo.owner, repo.name
+    );
+
+    let body = CreateMilestoneBody {
+        title: title.to_string(),
+        description: description.map(|s| s.to_string()),
+        due_on: due_on.map(|s| s.to_string()),
+    };
+
+    let response = post(&url, &body, token)?;
+
+    process_response(&response)?;
+
+    let response_body: Milestone = response.json()?;
+    Ok(response_body)
+}
+
+#[derive(Serialize, Debug)]
+struct CreateMilestoneBody {
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_on: Option<String>,
+}
+
+// https://docs.github.com/en/rest/reference/issues#update-a-milestone
+pub fn close_milestone(repo: &RemoteRepo, number: usize, token: &str) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/milestones/{}",
+        repo.owner, repo.name, number
+    );
+
+    let body = UpdateMilestoneBody {
+        state: "closed".to_string(),
+    };
+
+    let response = patch(&url, &body, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+#[derive(Serialize, Debug)]
+struct UpdateMilestoneBody {
+    state: String,
+}
+
+fn label_url(repo: &RemoteRepo, name: Option<&str>) -> Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(&format!(
+        "https://api.github.com/repos/{}/{}/labels",
+        repo.owner, repo.name
+    ))?;
+    if let Some(name) = name {
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid label url"))?
+            .push(name);
+    }
+    Ok(url)
+}
+
+// https://docs.github.com/en/rest/reference/issues#list-labels-for-a-repository
+pub fn get_labels(repo: &RemoteRepo, token: &str) -> Result<Vec<Label>> {
+    let mut labels = vec![];
+    let per_page = 100;
+
+    for page in 1.. {
+        let url = format!(
+            "{}?per_page={}&page={}",
+            label_url(repo, None)?,
+            per_page,
+            page
+        );
+
+        let response = get(&url, token, None)?;
+
+        process_response(&response)?;
+
+        let response_body: Vec<Label> = response.json()?;
+        let count = response_body.len();
+        labels.extend(response_body);
+
+        if count < per_page {
+            break;
+        }
+    }
+
+    Ok(labels)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Label {
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+}
+
+// https://docs.github.com/en/rest/reference/issues#create-a-label
+pub fn create_label(
+    repo: &RemoteRepo,
+    name: &str,
+    color: &str,
+    description: &str,
+    token: &str,
+) -> Result<()> {
+    let url = label_url(repo, None)?;
+
+    let body = LabelBody {
+        name: Some(name.to_string()),
+        new_name: None,
+        color: color.to_string(),
+        description: description.to_string(),
+    };
+
+    let response = post(url.as_str(), &body, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+// https://docs.github.com/en/rest/reference/issues#update-a-label
+pub fn update_label(
+    repo: &RemoteRepo,
+    name: &str,
+    new_name: &str,
+    color: &str,
+    description: &str,
+    token: &str,
+) -> Result<()> {
+    let url = label_url(repo, Some(name))?;
+
+    let body = LabelBody {
+        name: None,
+        new_name: Some(new_name.to_string()),
+        color: color.to_string(),
+        description: description.to_string(),
+    };
+
+    let response = patch(url.as_str(), &body, token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+// https://docs.github.com/en/rest/reference/issues#delete-a-label
+pub fn delete_label(repo: &RemoteRepo, name: &str, token: &str) -> Result<()> {
+    let url = label_url(repo, Some(name))?;
+
+    let response = delete(url.as_str(), token)?;
+
+    process_response(&response).map(|_| ())
+}
+
+#[derive(Serialize, Debug)]
+struct LabelBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_name: Option<String>,
+    color: String,
+    description: String,
+}
+
+// https://docs.github.com/en/rest/reference/users#get-a-user
+pub fn get_owner_type(login: &str, token: &str) -> Result<models::OwnerType> {
+    let url = format!("https://api.github.com/users/{}", login);
+
+    let response = get(&url, token, None)?;
+
+    process_response(&response)?;
+
+    let response_body: OwnerResponse = response.json()?;
+    Ok(response_body.owner_type)
+}
+
+#[derive(Deserialize, Debug)]
+struct OwnerResponse {
+    #[serde(rename = "type")]
+    owner_type: models::OwnerType,
+}
+
 fn process_response(response: &req::Response) -> Result<&req::Response> {
     let status = response.status();
 
@@ -2,6 +2,7 @@ mod cli;
 mod commands;
 mod config;
 mod convert;
+mod date;
 mod filter;
 mod git;
 mod github;
@@ -27,6 +28,7 @@ fn main() -> Result<()> {
     match args.command {
         Commands::Add(args) => args.run(),
         Commands::Apply(args) => args.run(),
+        Commands::ApplyConfig(args) => args.run(),
         Commands::Branch(args) => args.run(),
         Commands::Checkout(args) => args.run(),
         Commands::Ci(args) => args.run(),
@@ -35,18 +37,32 @@ fn main() -> Result<()> {
         Commands::Commit(args) => args.run(),
         Commands::Create(args) => args.run(),
         Commands::Fetch(args) => args.run(),
+        Commands::Grep(args) => args.run(),
         Commands::Hook(args) => args.run(),
         Commands::Init(args) => args.save_config(),
         Commands::Invite(args) => args.run(),
+        Commands::Issue(args) => args.run(),
+        Commands::Label(args) => args.run(),
         Commands::Merge(args) => args.run(),
+        Commands::Milestone(args) => args.run(),
+        Commands::Log(args) => args.run(),
         Commands::Make(args) => args.run(),
+        Commands::Plan(args) => args.run(),
+        Commands::Pr(args) => args.run(),
         Commands::Pull(args) => args.run(),
         Commands::Push(args) => args.run(),
+        Commands::Release(args) => args.run(),
         Commands::Remove(args) => args.run(),
         Commands::Rename(args) => args.run(),
+        Commands::Replace(args) => args.run(),
         Commands::Set(args) => args.run(),
         Commands::Show(args) => args.run(),
+        Commands::Snapshot(args) => args.run(),
+        Commands::Stash(args) => args.run(),
         Commands::Status(args) => args.run(),
+        Commands::Sync(args) => args.run(),
+        Commands::Tag(args) => args.run(),
+        Commands::Team(args) => args.run(),
         Commands::Template(args) => args.run(),
         Commands::Topic(args) => args.run(),
         Commands::Transfer(args) => args.run(),
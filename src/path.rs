@@ -22,6 +22,12 @@ pub fn user_path() -> Option<PathBuf> {
     Some(config)
 }
 
+/// File that keeps the positions of repositories before `checkout --at`
+pub fn checkout_restore_path(organisation: &str) -> Option<PathBuf> {
+    let dir = config_dir()?.join("restore").ensure_dir_exists().ok()?;
+    Some(dir.join(format!("{}.toml", organisation)))
+}
+
 pub fn local_path_repo(organisation: &str, name: &str, root: &str) -> PathBuf {
     let root_dir = Path::new(&root);
     root_dir.join(organisation).join(name)
@@ -40,6 +46,12 @@ pub fn local_path_org(organisation: &str, root: &str) -> anyhow::Result<PathBuf>
     Ok(local_path)
 }
 
+/// Directory where orphaned repositories of an organisation are moved to
+pub fn local_path_attic(organisation: &str, root: &str) -> PathBuf {
+    let root_dir = Path::new(&root);
+    root_dir.join(".attic").join(organisation)
+}
+
 pub trait EnsureDirExists: Sized {
     fn ensure_dir_exists(self) -> std::io::Result<Self>;
 }